@@ -0,0 +1,329 @@
+// Arguments for the `create_store` and `stride_sizes` macros.
+//
+// The arguments for `create_store` are named, e.g.:
+//
+// #[create_store(v4 = [5, 5, 4, 3, 3, 3, 3, 3, 3], v6 = [4; 32])]
+//
+// Each argument can be omitted, in which case the default is used. For
+// backwards compatibility the arguments can also be a tuple with the stride
+// sizes for IPv4 and IPv6, respectively, e.g.:
+//
+// #[create_store(([5, 5, 4, 3, 3, 3, 3, 3, 3], [4; 32]))]
+//
+// The stride sizes for an address family can be set to `None`, in which case
+// the store will not have a tree for that address family at all, e.g.:
+//
+// #[create_store(v4 = [4; 8], v6 = None)]
+//
+// Both macros take a `bucket_map` argument, that selects how the prefixes
+// and nodes of each length are spread over the storage levels, see
+// maps.rs. For `create_store` it is either one bucket map for both address
+// families, or a tuple with the bucket maps for IPv4 and IPv6, respectively,
+// e.g.:
+//
+// #[create_store(v4 = [4; 8], bucket_map = (legacy, uniform(8)))]
+//
+// Both macros take a `crate` argument with the path to the rotonda-store
+// crate as a string, e.g. `crate = "my_crate::rotonda_store"`, that is used
+// for all the names in the generated code. It defaults to `::rotonda_store`.
+// Inside rotonda-store itself it should be `crate = "crate"`.
+
+use proc_macro2::Span;
+use syn::ext::IdentExt;
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::{parse_quote, Token};
+
+pub struct StoreArgs {
+    // The stride sizes for the IPv4 tree, or None if the store should not
+    // have an IPv4 tree.
+    pub v4: Option<syn::Expr>,
+    // The stride sizes for the IPv6 tree, or None if the store should not
+    // have an IPv6 tree.
+    pub v6: Option<syn::Expr>,
+    // The bucket maps for IPv4 and IPv6, respectively, if any.
+    pub bucket_map: Option<(syn::Expr, syn::Expr)>,
+    // The path to the rotonda-store crate.
+    pub krate: syn::Path,
+}
+
+// The default path to the rotonda-store crate.
+pub fn default_crate() -> syn::Path {
+    parse_quote!(::rotonda_store)
+}
+
+// The default stride sizes for IPv4.
+pub fn default_v4() -> syn::Expr {
+    parse_quote!([5, 5, 4, 3, 3, 3, 3, 3, 3])
+}
+
+// The default stride sizes for IPv6.
+pub fn default_v6() -> syn::Expr {
+    parse_quote!([4; 32])
+}
+
+impl Parse for StoreArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        if input.peek(syn::token::Paren) {
+            return Self::parse_tuple(input);
+        }
+
+        let mut v4 = None;
+        let mut v6 = None;
+        let mut bucket_map = None;
+        let mut krate = None;
+
+        while !input.is_empty() {
+            let key = input.call(syn::Ident::parse_any)?;
+            input.parse::<Token![=]>()?;
+
+            if key == "crate" {
+                if krate.is_some() {
+                    return Err(syn::Error::new(key.span(), "duplicate argument `crate`"));
+                }
+                krate = Some(parse_crate_path(input)?);
+                if input.is_empty() {
+                    break;
+                }
+                input.parse::<Token![,]>()?;
+                continue;
+            }
+
+            if key == "bucket_map" {
+                if bucket_map.is_some() {
+                    return Err(syn::Error::new(
+                        key.span(),
+                        "duplicate argument `bucket_map`",
+                    ));
+                }
+                bucket_map = Some(family_bucket_maps(input.parse()?)?);
+                if input.is_empty() {
+                    break;
+                }
+                input.parse::<Token![,]>()?;
+                continue;
+            }
+
+            let value = input.parse::<syn::Expr>()?;
+            let arg = match key.to_string().as_str() {
+                "v4" => &mut v4,
+                "v6" => &mut v6,
+                _ => {
+                    return Err(syn::Error::new(
+                        key.span(),
+                        format!(
+                            "unknown argument `{}`, expected one of `v4`, \
+                            `v6`, `bucket_map`, `crate`",
+                            key
+                        ),
+                    ))
+                }
+            };
+            if arg.is_some() {
+                return Err(syn::Error::new(
+                    key.span(),
+                    format!("duplicate argument `{}`", key),
+                ));
+            }
+            *arg = Some(family_strides(value));
+
+            if input.is_empty() {
+                break;
+            }
+            input.parse::<Token![,]>()?;
+        }
+
+        StoreArgs {
+            v4: v4.unwrap_or_else(|| Some(default_v4())),
+            v6: v6.unwrap_or_else(|| Some(default_v6())),
+            bucket_map,
+            krate: krate.unwrap_or_else(default_crate),
+        }
+        .check_families()
+    }
+}
+
+impl StoreArgs {
+    // Parses the tuple form of the arguments: `(v4_strides, v6_strides)`,
+    // optionally followed by a `crate` argument.
+    fn parse_tuple(input: ParseStream) -> syn::Result<Self> {
+        let expr = input.parse::<syn::Expr>()?;
+        let mut krate = None;
+        if !input.is_empty() {
+            input.parse::<Token![,]>()?;
+            if !input.is_empty() {
+                krate = Some(parse_crate_arg(input)?);
+                if !input.is_empty() {
+                    input.parse::<Token![,]>()?;
+                }
+            }
+        }
+        if !input.is_empty() {
+            return Err(input.error("unexpected argument after the tuple"));
+        }
+        let elems = match expr {
+            syn::Expr::Tuple(tuple) if tuple.elems.len() == 2 => tuple.elems,
+            expr => {
+                return Err(syn::Error::new_spanned(
+                    &expr,
+                    "expected a tuple with the stride sizes for IPv4 and \
+                    IPv6, e.g. `([5, 5, 4, 3, 3, 3, 3, 3, 3], [4; 32])`, \
+                    or named arguments, e.g. `v4 = [4; 8], v6 = [4; 32]`",
+                ))
+            }
+        };
+
+        let mut elems = elems.into_iter().map(family_strides);
+        StoreArgs {
+            v4: elems.next().unwrap(),
+            v6: elems.next().unwrap(),
+            bucket_map: None,
+            krate: krate.unwrap_or_else(default_crate),
+        }
+        .check_families()
+    }
+
+    // Errors out if the store would not have a tree for any address family.
+    fn check_families(self) -> syn::Result<Self> {
+        if self.v4.is_none() && self.v6.is_none() {
+            return Err(syn::Error::new(
+                Span::call_site(),
+                "the store needs the stride sizes for at least one address \
+                family",
+            ));
+        }
+        Ok(self)
+    }
+}
+
+// Returns None if `strides` is `None`, i.e. the address family should not be
+// in the store, and the stride sizes otherwise.
+fn family_strides(strides: syn::Expr) -> Option<syn::Expr> {
+    match &strides {
+        syn::Expr::Path(p) if p.qself.is_none() && p.path.is_ident("None") => None,
+        _ => Some(strides),
+    }
+}
+
+// Returns the bucket maps for IPv4 and IPv6 from the value of a
+// `bucket_map` argument for `create_store`.
+fn family_bucket_maps(map: syn::Expr) -> syn::Result<(syn::Expr, syn::Expr)> {
+    match map {
+        syn::Expr::Tuple(tuple) if tuple.elems.len() == 2 => {
+            let mut elems = tuple.elems.into_iter();
+            Ok((elems.next().unwrap(), elems.next().unwrap()))
+        }
+        syn::Expr::Tuple(tuple) => Err(syn::Error::new_spanned(
+            tuple,
+            "expected a tuple with the bucket maps for IPv4 and IPv6, e.g. \
+            `(legacy, uniform(8))`, or one bucket map for both",
+        )),
+        map => Ok((map.clone(), map)),
+    }
+}
+
+pub struct StrideArgs {
+    // The address family, either `IPv4` or `IPv6`.
+    pub af: syn::Expr,
+    // The stride sizes, an array or the name of a const defined with
+    // `strides!`.
+    pub strides: syn::Expr,
+    // The bucket map, if any.
+    pub bucket_map: Option<syn::Expr>,
+    // The path to the rotonda-store crate.
+    pub krate: syn::Path,
+}
+
+// The arguments for the `stride_sizes` macro, either as a tuple, e.g.
+// `((IPv4, [4; 8]))`, or as a list, e.g. `(IPv4, [4; 8])`, with an optional
+// `bucket_map` and `crate` argument anywhere in the list, e.g.
+// `(IPv4, [4; 8], bucket_map = uniform(8), crate = "crate")`.
+impl Parse for StrideArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut args = Punctuated::<syn::Expr, Token![,]>::new();
+        let mut bucket_map = None;
+        let mut krate = None;
+
+        while !input.is_empty() {
+            if input.peek(Token![crate]) && input.peek2(Token![=]) {
+                let span = input.span();
+                if krate.is_some() {
+                    return Err(syn::Error::new(span, "duplicate argument `crate`"));
+                }
+                krate = Some(parse_crate_arg(input)?);
+            } else if input.peek(syn::Ident) && input.peek2(Token![=]) {
+                let key = input.parse::<syn::Ident>()?;
+                if key != "bucket_map" {
+                    return Err(syn::Error::new(
+                        key.span(),
+                        format!(
+                            "unknown argument `{}`, expected one of \
+                            `bucket_map`, `crate`",
+                            key
+                        ),
+                    ));
+                }
+                if bucket_map.is_some() {
+                    return Err(syn::Error::new(
+                        key.span(),
+                        "duplicate argument `bucket_map`",
+                    ));
+                }
+                input.parse::<Token![=]>()?;
+                bucket_map = Some(input.parse()?);
+            } else {
+                args.push(input.parse()?);
+            }
+
+            if input.is_empty() {
+                break;
+            }
+            input.parse::<Token![,]>()?;
+        }
+
+        let args = match args.first() {
+            Some(syn::Expr::Tuple(t)) if args.len() == 1 => t.elems.clone(),
+            _ => args,
+        };
+
+        if args.len() != 2 {
+            return Err(syn::Error::new_spanned(
+                &args,
+                "expected an address family and an array of stride sizes, \
+                e.g. `(IPv4, [5, 5, 4, 3, 3, 3, 3, 3, 3])`",
+            ));
+        }
+
+        let mut args = args.into_iter();
+        Ok(StrideArgs {
+            af: args.next().unwrap(),
+            strides: args.next().unwrap(),
+            bucket_map,
+            krate: krate.unwrap_or_else(default_crate),
+        })
+    }
+}
+
+// Parses a `crate = "path"` argument.
+fn parse_crate_arg(input: ParseStream) -> syn::Result<syn::Path> {
+    input.parse::<Token![crate]>()?;
+    input.parse::<Token![=]>()?;
+    parse_crate_path(input)
+}
+
+// Parses the value of a `crate` argument: a string literal with the path to
+// the rotonda-store crate.
+pub fn parse_crate_path(input: ParseStream) -> syn::Result<syn::Path> {
+    let lit = input.parse::<syn::LitStr>()?;
+    lit.parse_with(syn::Path::parse_mod_style)
+}
+
+// Returns `krate` as a `crate` argument, so that it can be handed to the
+// `stride_sizes` macro.
+pub fn crate_arg(krate: &syn::Path) -> proc_macro2::TokenStream {
+    let lit = syn::LitStr::new(
+        &quote::quote!(#krate).to_string().replace(' ', ""),
+        Span::call_site(),
+    );
+    quote::quote! { crate = #lit }
+}
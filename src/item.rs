@@ -0,0 +1,80 @@
+// The struct annotated with the `create_store` macro.
+//
+// The generated store takes over the visibility of the annotated struct, and
+// its attributes:
+//
+// * doc comments are put in front of the generated documentation for the
+//   store;
+// * `cfg` attributes are put on every item that is generated for the store,
+//   so that a store can be feature-gated as a whole;
+// * the other permitted attributes, see PERMITTED_ATTRS, are put on the
+//   store struct only.
+//
+// Any other attribute results in an error, since we cannot tell whether it
+// would apply to the generated store.
+
+use syn::spanned::Spanned;
+
+// The attributes, other than `doc` and `cfg`, that are carried through to
+// the store struct.
+const PERMITTED_ATTRS: [&str; 7] = [
+    "allow", "cfg_attr", "deny", "derive", "expect", "must_use", "warn",
+];
+
+pub struct StoreItem {
+    pub vis: syn::Visibility,
+    pub ident: syn::Ident,
+    // The `#[doc]` attributes, i.e. the doc comments.
+    pub docs: Vec<syn::Attribute>,
+    // The `#[cfg]` attributes.
+    pub cfgs: Vec<syn::Attribute>,
+    // The other permitted attributes.
+    pub attrs: Vec<syn::Attribute>,
+}
+
+impl TryFrom<syn::ItemStruct> for StoreItem {
+    type Error = syn::Error;
+
+    fn try_from(item: syn::ItemStruct) -> syn::Result<Self> {
+        let mut docs = vec![];
+        let mut cfgs = vec![];
+        let mut attrs = vec![];
+        let mut errors: Option<syn::Error> = None;
+
+        for attr in item.attrs {
+            let path = attr.path();
+            if path.is_ident("doc") {
+                docs.push(attr);
+            } else if path.is_ident("cfg") {
+                cfgs.push(attr);
+            } else if PERMITTED_ATTRS.iter().any(|name| path.is_ident(name)) {
+                attrs.push(attr);
+            } else {
+                let err = syn::Error::new(
+                    attr.span(),
+                    format!(
+                        "unsupported attribute on a store, expected one of \
+                        `doc`, `cfg`, `{}`",
+                        PERMITTED_ATTRS.join("`, `")
+                    ),
+                );
+                match errors.as_mut() {
+                    Some(errors) => errors.combine(err),
+                    None => errors = Some(err),
+                }
+            }
+        }
+
+        if let Some(errors) = errors {
+            return Err(errors);
+        }
+
+        Ok(StoreItem {
+            vis: item.vis,
+            ident: item.ident,
+            docs,
+            cfgs,
+            attrs,
+        })
+    }
+}
@@ -1,151 +1,185 @@
 extern crate proc_macro;
 
+mod args;
+mod item;
 mod maps;
+mod meta;
+#[cfg(feature = "mrt")]
+mod mrt;
+#[cfg(feature = "snapshot")]
+mod snapshot;
+mod strides;
+#[cfg(test)]
+mod tests;
 
 use proc_macro::TokenStream;
 use quote::{format_ident, quote};
 use std::iter::Iterator;
-use syn::parse_macro_input;
 
 #[proc_macro_attribute]
 pub fn stride_sizes(attr: TokenStream, input: TokenStream) -> TokenStream {
-    // The arguments for the macro invocation
-    let attrs = parse_macro_input!(attr as syn::ExprTuple);
+    expand_stride_sizes(attr.into(), input.into()).into()
+}
 
-    let attrs = attrs.elems.iter().collect::<Vec<_>>();
+// The macros are implemented on proc_macro2 token streams, so that their
+// expansion can be tested outside of the compiler.
+fn expand_stride_sizes(
+    attr: proc_macro2::TokenStream,
+    input: proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    let args = match syn::parse2::<crate::args::StrideArgs>(attr) {
+        Ok(args) => args,
+        Err(err) => return err.to_compile_error(),
+    };
+    let krate = &args.krate;
 
-    let input = parse_macro_input!(input as syn::ItemStruct);
+    let input = match syn::parse2::<syn::ItemStruct>(input) {
+        Ok(input) => input,
+        Err(err) => return err.to_compile_error(),
+    };
     let type_name = &input.ident;
-    let ip_af = match attrs[0] {
-        syn::Expr::Path(t) => t,
-        _ => panic!("Expected Family Type"),
+
+    let af_bits = match crate::strides::af_bits(&args.af) {
+        Ok(af_bits) => af_bits,
+        Err(err) => return err.to_compile_error(),
     };
-    let prefixes_all_len;
-    let all_len;
-    let prefixes_buckets_name: syn::Ident;
-    // let prefix_store_bits;
-    let get_root_prefix_set;
+
+    // `af_bits` only accepts the `IPv4` and `IPv6` paths.
+    let af_name = if af_bits == 32 {
+        format_ident!("IPv4")
+    } else {
+        format_ident!("IPv6")
+    };
+    let ip_af = quote! { #krate::prelude::#af_name };
+
+    // A const defined with `strides!`: have the macro that comes with it
+    // hand the stride sizes back to us as an array literal. The macro has
+    // the same path as the const, so the const can be in another module.
+    if let syn::Expr::Path(syn::ExprPath {
+        path, qself: None, ..
+    }) = &args.strides
+    {
+        let crate_arg = crate::args::crate_arg(krate);
+        let bucket_map_arg = args
+            .bucket_map
+            .as_ref()
+            .map(|map| quote! { bucket_map = #map, });
+        return quote! {
+            const _: &[u8] = &#path;
+
+            #path! {
+                { #krate::prelude::multi::stride_sizes }
+                (#crate_arg, #bucket_map_arg #af_name,)
+                #input
+            }
+        };
+    }
+
+    let mut strides_num = match crate::strides::parse_strides(&args.strides, af_bits) {
+        Ok(strides_num) => strides_num,
+        Err(err) => return err.to_compile_error(),
+    };
+
+    let bucket_map = match &args.bucket_map {
+        Some(map) => match crate::maps::BucketMap::parse(map, af_bits) {
+            Ok(bucket_map) => bucket_map,
+            Err(err) => return err.to_compile_error(),
+        },
+        None => crate::maps::BucketMap::default(),
+    };
+
+    // All the prefix lengths for the address family, and the names of the
+    // PrefixSet fields for each length in the PrefixBuckets struct, p0, p1,
+    // ... p32 for IPv4 and p0, p1, ... p128 for IPv6.
+    let all_len = (0..=af_bits).collect::<Vec<_>>();
+    let prefixes_all_len = all_len
+        .iter()
+        .map(|l| format_ident!("p{}", l))
+        .collect::<Vec<_>>();
 
     // The name of the Struct that we're going to generate
     // We'll prepend it with the name of the TreeBitMap struct
     // that the user wants, so that our macro is a little bit
     // more hygienic, and the user can create multiple types
     // of TreeBitMap structs with different stride sizes.
-    let buckets_name = if ip_af.path.is_ident("IPv4") {
+    let buckets_name = if af_bits == 32 {
         format_ident!("{}NodeBuckets4", type_name)
     } else {
         format_ident!("{}NodeBuckets6", type_name)
     };
-    let store_bits = if ip_af.path.is_ident("IPv4") {
-        all_len = (0..=32_u8).collect::<Vec<_>>();
-        prefixes_all_len = (0..=32_u8)
-            .map(|l| format_ident!("p{}", l))
-            .collect::<Vec<_>>();
-        prefixes_buckets_name = format_ident!("PrefixBuckets4");
-        // prefix_store_bits = format_ident!("prefix_store_bits_4");
-        get_root_prefix_set = quote! {
-            fn get_root_prefix_set(&self, len: u8) -> &'_ PrefixSet<IPv4, M> {
-                [
-                    &self.p0, &self.p1, &self.p2, &self.p3, &self.p4, &self.p5, &self.p6, &self.p7, &self.p8,
-                    &self.p9, &self.p10, &self.p11, &self.p12, &self.p13, &self.p14, &self.p15, &self.p16,
-                    &self.p17, &self.p18, &self.p19, &self.p20, &self.p21, &self.p22, &self.p23, &self.p24,
-                    &self.p25, &self.p26, &self.p27, &self.p28, &self.p29, &self.p30, &self.p31, &self.p32
-                ][len as usize]
-            }
-        };
-        crate::maps::node_buckets_map_v4()
+    let prefixes_buckets_name = if af_bits == 32 {
+        format_ident!("PrefixBuckets4")
     } else {
-        all_len = (0..=128_u8).collect::<Vec<_>>();
-        prefixes_all_len = (0..=128_u8)
-            .map(|l| format_ident!("p{}", l))
-            .collect::<Vec<_>>();
-
-        prefixes_buckets_name = format_ident!("PrefixBuckets6");
-        // prefix_store_bits = format_ident!("prefix_store_bits_6");
-        get_root_prefix_set = quote! {
-            fn get_root_prefix_set(&self, len: u8) -> &'_ PrefixSet<IPv6, M> {
-                [
-                    &self.p0, &self.p1, &self.p2, &self.p3, &self.p4, &self.p5, &self.p6, &self.p7, &self.p8,
-                    &self.p9, &self.p10, &self.p11, &self.p12, &self.p13, &self.p14, &self.p15, &self.p16,
-                    &self.p17, &self.p18, &self.p19, &self.p20, &self.p21, &self.p22, &self.p23, &self.p24,
-                    &self.p25, &self.p26, &self.p27, &self.p28, &self.p29, &self.p30, &self.p31, &self.p32,
-                    &self.p33, &self.p34, &self.p35, &self.p36, &self.p37, &self.p38, &self.p39, &self.p40,
-                    &self.p41, &self.p42, &self.p43, &self.p44, &self.p45, &self.p46, &self.p47, &self.p48,
-                    &self.p49, &self.p50, &self.p51, &self.p52, &self.p53, &self.p54, &self.p55, &self.p56,
-                    &self.p57, &self.p58, &self.p59, &self.p60, &self.p61, &self.p62, &self.p63, &self.p64,
-                    &self.p65, &self.p66, &self.p67, &self.p68, &self.p69, &self.p70, &self.p71, &self.p72,
-                    &self.p73, &self.p74, &self.p75, &self.p76, &self.p77, &self.p78, &self.p79, &self.p80,
-                    &self.p81, &self.p82, &self.p83, &self.p84, &self.p85, &self.p86, &self.p87, &self.p88,
-                    &self.p89, &self.p90, &self.p91, &self.p92, &self.p93, &self.p94, &self.p95, &self.p96,
-                    &self.p97, &self.p98, &self.p99, &self.p100, &self.p101, &self.p102, &self.p103, &self.p104,
-                    &self.p105, &self.p106, &self.p107, &self.p108, &self.p109, &self.p110, &self.p111, &self.p112,
-                    &self.p113, &self.p114, &self.p115, &self.p116, &self.p117, &self.p118, &self.p119, &self.p120,
-                    &self.p121, &self.p122, &self.p123, &self.p124, &self.p125, &self.p126, &self.p127, &self.p128
-                    ][len as usize]
-            }
-        };
-        crate::maps::node_buckets_map_v6()
+        format_ident!("PrefixBuckets6")
     };
+    let store_bits = crate::maps::node_buckets_map(&bucket_map, af_bits);
 
-    let mut strides_num: Vec<u8> = vec![];
     let mut strides = vec![];
-    let mut strides_all_len = vec![];
     let mut strides_all_len_accu: Vec<u8> = vec![];
     let mut strides_all_len_level = vec![];
-    let mut strides_len3 = vec![];
-    let mut strides_len3_l = vec![];
-    let mut strides_len4 = vec![];
-    let mut strides_len4_l = vec![];
-    let mut strides_len5 = vec![];
-    let mut strides_len5_l = vec![];
-
     let mut s_accu = 0_u8;
 
-    let attrs_s = match attrs[1] {
-        syn::Expr::Array(arr) => arr,
-        _ => panic!("Expected an array"),
-    };
-    let strides_len = attrs_s.elems.len() as u8;
-    let first_stride_size = &attrs_s.elems[0];
-
-    for (len, stride) in attrs_s.elems.iter().enumerate() {
-        strides_all_len.push(format_ident!("l{}", len));
-
-        match stride {
-            syn::Expr::Lit(s) => {
-                if let syn::Lit::Int(i) = &s.lit {
-                    let stride_len = i.base10_digits().parse::<u8>().unwrap();
-                    strides_num.push(stride_len);
-                    strides_all_len_level.push(format_ident!("l{}", s_accu));
-
-                    match stride_len {
-                        3 => {
-                            strides_len3.push(s_accu as usize);
-                            strides_len3_l.push(format_ident!("l{}", s_accu));
-                        }
-                        4 => {
-                            strides_len4.push(s_accu as usize);
-                            strides_len4_l.push(format_ident!("l{}", s_accu));
-                        }
-                        5 => {
-                            strides_len5.push(s_accu as usize);
-                            strides_len5_l.push(format_ident!("l{}", s_accu));
-                        }
-                        _ => panic!("Expected a stride of 3, 4 or 5"),
-                    };
-                    strides_all_len_accu.push(s_accu);
+    let strides_len = strides_num.len() as u8;
+    let first_stride_size = strides_num[0];
+
+    // The stride sizes have been validated at this point, so they are all
+    // one of the supported sizes, and they add up to the number of bits of
+    // the address family.
+    for stride_len in strides_num.iter().copied() {
+        strides_all_len_level.push(format_ident!("l{}", s_accu));
+        strides_all_len_accu.push(s_accu);
+
+        s_accu += stride_len;
+        let stride_type = format_ident!("Stride{}", stride_len);
+        strides.push(quote! { #krate::prelude::multi::#stride_type })
+    }
 
-                    s_accu += stride_len;
-                    strides.push(format_ident!("Stride{}", stride_len))
-                } else {
-                    panic!("Expected an integer")
+    // The accessors for the NodeSets, one for each stride size the store
+    // supports, e.g. for stride size 4:
+    //
+    // fn get_store4(&self, id: StrideNodeId<IPv4>) -> &NodeSet<IPv4, Stride4> {
+    //     match id.get_id().1 as usize {
+    //         10 => &self.l10,
+    //         ...
+    //         _ => panic!(..)
+    //     }
+    // }
+    //
+    // The match arms are the levels that start a stride of that size. The
+    // accessors for stride sizes that are not used in the strides for this
+    // store only have the panicking arm.
+    let get_stores = crate::strides::STRIDE_SIZES
+        .iter()
+        .map(|stride_size| {
+            let get_store = format_ident!("get_store{}", stride_size);
+            let stride_type = format_ident!("Stride{}", stride_size);
+            let stride_type = quote! { #krate::prelude::multi::#stride_type };
+            let (levels, fields): (Vec<_>, Vec<_>) = strides_all_len_accu
+                .iter()
+                .zip(strides_all_len_level.iter())
+                .zip(strides_num.iter())
+                .filter(|(_, s)| *s == stride_size)
+                .map(|((accu, level), _)| (*accu as usize, level))
+                .unzip();
+            let panic_msg = format!(
+                "unexpected sub prefix length {{}} in stride size {} ({{}})",
+                stride_size
+            );
+
+            quote! {
+                fn #get_store(&self, id: #krate::prelude::multi::StrideNodeId<#ip_af>) -> &#krate::prelude::multi::NodeSet<#ip_af, #stride_type> {
+                    match id.get_id().1 as usize {
+                        #( #levels => &self.#fields, )*
+                        _ => panic!(
+                            #panic_msg,
+                            id.get_id().1,
+                            id
+                        ),
+                    }
                 }
             }
-            _ => {
-                panic!("Expected a literal")
-            }
-        }
-    }
+        })
+        .collect::<Vec<_>>();
 
     // Check if the strides division makes sense
     let mut len_to_stride_arr = [0_u8; 128];
@@ -167,7 +201,7 @@ pub fn stride_sizes(attr: TokenStream, input: TokenStream) -> TokenStream {
     let struct_creation = quote! {
 
         #[derive(Debug)]
-        pub(crate) struct #buckets_name<AF: AddressFamily> {
+        pub(crate) struct #buckets_name<AF: #krate::prelude::AddressFamily> {
             // created fields for each sub-prefix (StrideNodeId) length,
             // with hard-coded field-names, like this:
             // l0: NodeSet<AF, Stride5>,
@@ -175,57 +209,72 @@ pub fn stride_sizes(attr: TokenStream, input: TokenStream) -> TokenStream {
             // l10: NodeSet<AF, Stride4>,
             // ...
             // l29: NodeSet<AF, Stride3>
-            # ( #strides_all_len_level: NodeSet<#ip_af, #strides>, )*
-            _af: PhantomData<AF>,
+            # ( #strides_all_len_level: #krate::prelude::multi::NodeSet<#ip_af, #strides>, )*
+            _af: ::std::marker::PhantomData<AF>,
             stride_sizes: [u8; 42],
             strides_len: u8
         }
 
         #[derive(Debug)]
-        pub(crate) struct #prefixes_buckets_name<AF: AddressFamily, M: Meta> {
+        pub(crate) struct #prefixes_buckets_name<AF: #krate::prelude::AddressFamily, M: #krate::prelude::Meta> {
             // creates a bucket for each prefix (PrefixId) length, with
             // hard-coded field-names, like this:
             // p0: PrefixSet<AF, M>,
             // p1: PrefixSet<AF, M>,
             // ...
             // p32: PrefixSet<AF, M>,
-            #( #prefixes_all_len: PrefixSet<#ip_af, M>, )*
-            _af: PhantomData<AF>,
-            _m: PhantomData<M>,
+            #( #prefixes_all_len: #krate::prelude::multi::PrefixSet<#ip_af, M>, )*
+            _af: ::std::marker::PhantomData<AF>,
+            _m: ::std::marker::PhantomData<M>,
         }
 
     };
 
-    let prefix_buckets_map = if ip_af.path.is_ident("IPv4") {
-        crate::maps::prefix_buckets_map_v4()
-    } else {
-        crate::maps::prefix_buckets_map_v6()
-    };
+    let prefix_buckets_map = crate::maps::prefix_buckets_map(&bucket_map, af_bits);
 
     let prefix_buckets_impl = quote! {
 
-        impl<AF: AddressFamily, M: Meta> PrefixBuckets<#ip_af, M> for #prefixes_buckets_name<AF, M> {
+        impl<AF: #krate::prelude::AddressFamily, M: #krate::prelude::Meta> #krate::prelude::multi::PrefixBuckets<#ip_af, M> for #prefixes_buckets_name<AF, M> {
             fn init() -> #prefixes_buckets_name<AF, M> {
                 #prefixes_buckets_name {
-                    #( #prefixes_all_len: PrefixSet::init(1 << #prefixes_buckets_name::<AF, M>::get_bits_for_len(#all_len, 0)), )*
-                    _af: PhantomData,
-                    _m: PhantomData,
+                    #( #prefixes_all_len: #krate::prelude::multi::PrefixSet::init(1 << #prefixes_buckets_name::<AF, M>::get_bits_for_len(#all_len, 0)), )*
+                    _af: ::std::marker::PhantomData,
+                    _m: ::std::marker::PhantomData,
                 }
             }
 
-            fn remove(&mut self, id: PrefixId<#ip_af>) -> Option<M> { unimplemented!() }
+            fn remove(&mut self, id: #krate::prelude::multi::PrefixId<#ip_af>) -> Option<M> { unimplemented!() }
 
-            #get_root_prefix_set
+            fn get_root_prefix_set(&self, len: u8) -> &'_ #krate::prelude::multi::PrefixSet<#ip_af, M> {
+                [ #( &self.#prefixes_all_len, )* ][len as usize]
+            }
 
             #prefix_buckets_map
 
         }
 
+        impl<AF: #krate::prelude::AddressFamily, M: #krate::prelude::Meta> #prefixes_buckets_name<AF, M> {
+            // The mutable counterpart of `get_root_prefix_set`.
+            pub(crate) fn get_root_prefix_set_mut(&mut self, len: u8) -> &'_ mut #krate::prelude::multi::PrefixSet<#ip_af, M> {
+                match len {
+                    #( #all_len => &mut self.#prefixes_all_len, )*
+                    _ => panic!("unexpected prefix length {}", len),
+                }
+            }
+
+            // Returns an iterator over all the root PrefixSets, with the
+            // prefix length they hold the prefixes for, starting at
+            // length 0.
+            pub(crate) fn root_prefix_sets(&self) -> impl Iterator<Item = (u8, &'_ #krate::prelude::multi::PrefixSet<#ip_af, M>)> {
+                [ #( (#all_len, &self.#prefixes_all_len), )* ].into_iter()
+            }
+        }
+
     };
 
     let struct_impl = quote! {
 
-        impl<AF: AddressFamily> NodeBuckets<#ip_af> for #buckets_name<AF> {
+        impl<AF: #krate::prelude::AddressFamily> #krate::prelude::multi::NodeBuckets<#ip_af> for #buckets_name<AF> {
             fn init() -> Self {
                 #buckets_name {
                     // creates l0, l1, ... l<AF::BITS>, but only for the
@@ -234,50 +283,14 @@ pub fn stride_sizes(attr: TokenStream, input: TokenStream) -> TokenStream {
                     // l17, l20, l23, l26, l29 last level will be omitted,
                     // because that will never be used (l29 has children
                     // with prefixes up to prefix-length 32 in this example).
-                    #( #strides_all_len_level: NodeSet::init(#buckets_name::<AF>::len_to_store_bits(#strides_all_len_accu, 0) ), )*
-                    _af: PhantomData,
+                    #( #strides_all_len_level: #krate::prelude::multi::NodeSet::init(#buckets_name::<AF>::len_to_store_bits(#strides_all_len_accu, 0) ), )*
+                    _af: ::std::marker::PhantomData,
                     stride_sizes: [ #( #stride_sizes, )*],
                     strides_len: #strides_len
                 }
             }
 
-            fn get_store3(&self, id: StrideNodeId<#ip_af>) -> &NodeSet<#ip_af, Stride3> {
-                match id.get_id().1 as usize {
-                    #( #strides_len3 => &self.#strides_len3_l, )*
-                    _ => panic!(
-                        "unexpected sub prefix length {} in stride size 3 ({})",
-                        id.get_id().1,
-                        id
-                    ),
-                }
-            }
-
-            fn get_store4(&self, id: StrideNodeId<#ip_af>) -> &NodeSet<#ip_af, Stride4> {
-                match id.get_id().1 as usize {
-                    #( #strides_len4 => &self.#strides_len4_l, )*
-                    // ex.:
-                    // 10 => &self.l10,
-                    _ => panic!(
-                        "unexpected sub prefix length {} in stride size 4 ({})",
-                        id.get_id().1,
-                        id
-                    ),
-                }
-            }
-
-            fn get_store5(&self, id: StrideNodeId<#ip_af>) -> &NodeSet<#ip_af, Stride5> {
-                match id.get_id().1 as usize {
-                    #( #strides_len5 => &self.#strides_len5_l, )*
-                    // ex.:
-                    // 0 => &self.l0,
-                    // 5 => &self.l5,
-                    _ => panic!(
-                        "unexpected sub prefix length {} in stride size 5 ({})",
-                        id.get_id().1,
-                        id
-                    ),
-                }
-            }
+            #( #get_stores )*
 
             #[inline]
             fn get_stride_sizes(&self) -> &[u8] {
@@ -285,7 +298,7 @@ pub fn stride_sizes(attr: TokenStream, input: TokenStream) -> TokenStream {
             }
 
             #[inline]
-            fn get_stride_for_id(&self, id: StrideNodeId<#ip_af>) -> u8 {
+            fn get_stride_for_id(&self, id: #krate::prelude::multi::StrideNodeId<#ip_af>) -> u8 {
                 [ #(#len_to_stride_arr, )* ][id.get_id().1 as usize]
             }
 
@@ -304,7 +317,7 @@ pub fn stride_sizes(attr: TokenStream, input: TokenStream) -> TokenStream {
     };
 
     let type_alias = quote! {
-        type #type_name<M> = TreeBitMap<#ip_af, M, #buckets_name<#ip_af>, #prefixes_buckets_name<#ip_af, M>>;
+        type #type_name<M> = #krate::prelude::multi::TreeBitMap<#ip_af, M, #buckets_name<#ip_af>, #prefixes_buckets_name<#ip_af, M>>;
     };
 
     let result = quote! {
@@ -314,7 +327,93 @@ pub fn stride_sizes(attr: TokenStream, input: TokenStream) -> TokenStream {
         #type_alias
     };
 
-    TokenStream::from(result)
+    result
+}
+
+// ---------- Named stride arrays ---------------------------------------------
+
+/// Defines one or more const arrays of stride sizes, that can be used by name
+/// as the stride sizes argument for [`create_store`] and [`stride_sizes`].
+///
+/// The macros can not look up the value of a regular const, so a const
+/// that holds stride sizes for a store has to be defined with this macro.
+/// Each const is defined as a `[u8; N]`, and the stride sizes can be written
+/// as an array, e.g. `[5, 5, 4, 3]`, or as a repeat expression, e.g.
+/// `[4; 8]`. The stride sizes are checked when the const is used for an
+/// address family.
+///
+/// Next to the const this defines a hidden `macro_rules!` macro with the
+/// same name and path, so the const can be used by name or by path, e.g.
+/// `layouts::IP4_STRIDE_ARRAY`, also from other modules. The macro has the
+/// visibility of the const, but at most `pub(crate)`, so a const defined
+/// with `strides!` can not be used by other crates.
+///
+/// # Example
+/// ```ignore
+/// use rotonda_store::prelude::*;
+/// use rotonda_store::prelude::multi::*;
+/// use rotonda_store::meta_examples::PrefixAs;
+///
+/// strides! {
+///     const IP4_STRIDE_ARRAY = [4; 8];
+///     const IP6_STRIDE_ARRAY = [4; 32];
+/// }
+///
+/// #[create_store(v4 = IP4_STRIDE_ARRAY, v6 = IP6_STRIDE_ARRAY)]
+/// struct NuStorage;
+/// ```
+#[proc_macro]
+pub fn strides(input: TokenStream) -> TokenStream {
+    expand_strides(input.into()).into()
+}
+
+fn expand_strides(input: proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+    let consts = match syn::parse2::<crate::strides::StridesConsts>(input) {
+        Ok(consts) => consts,
+        Err(err) => return err.to_compile_error(),
+    };
+
+    let consts = consts.0.iter().map(|c| {
+        let attrs = &c.attrs;
+        let vis = &c.vis;
+        let ident = &c.ident;
+        let len = c.strides.len();
+        let strides = c.strides.iter().map(|(stride_len, span)| {
+            let mut lit = proc_macro2::Literal::u8_unsuffixed(*stride_len);
+            lit.set_span(*span);
+            lit
+        });
+        let strides = quote! { [ #( #strides ),* ] };
+
+        // The macro is imported under the name of the const, which only
+        // takes the macro namespace, as a `macro_rules!` macro can not be
+        // exported from the crate.
+        let macro_ident = format_ident!("__{}_strides", ident);
+        let macro_vis = match vis {
+            syn::Visibility::Public(_) => quote! { pub(crate) },
+            vis => quote! { #vis },
+        };
+
+        quote! {
+            #( #attrs )*
+            #vis const #ident: [u8; #len] = #strides;
+
+            #[doc(hidden)]
+            #[allow(unused_macros)]
+            macro_rules! #macro_ident {
+                ({ $($attr:tt)* } ( $($pre:tt)* ) $item:item) => {
+                    #[$($attr)* ( $($pre)* #strides )]
+                    $item
+                };
+            }
+
+            #[doc(hidden)]
+            #[allow(unused_imports)]
+            #macro_vis use #macro_ident as #ident;
+        }
+    });
+
+    quote! { #( #consts )* }
 }
 
 // ---------- Create Store struct -------------------------------------------
@@ -326,67 +425,547 @@ pub fn stride_sizes(attr: TokenStream, input: TokenStream) -> TokenStream {
 /// that can used as a store type.
 ///
 /// # Usage
-/// ```
+/// ```ignore
 /// use rotonda_store::prelude::*;
 /// use rotonda_store::prelude::multi::*;
 /// use rotonda_store::meta_examples::PrefixAs;
 ///
-/// const IP4_STRIDE_ARRAY = [4; 8];
-/// const IP6_STRIDE_ARRAY = [4; 32];
-///
-/// #[create_store((IPV4_STRIDE_ARRAY, IPV6_STRIDE_ARRAY))]
+/// #[create_store(v4 = [4; 8], v6 = [4; 32])]
 /// struct NuStorage;
 /// ```
 ///
 /// This will create a `NuStorage` struct, that can be used as a regular
 /// store.
 ///
-/// The stride-sizes can be any of \[3,4,5\], and they should add up
-/// to the total number of bits in the address family (32 for IPv4 and
-/// 128 for IPv6). Stride sizes in the array will be repeated if the sum
+/// The store has the visibility of the annotated struct. Doc comments on
+/// the struct are put in front of the generated documentation for the
+/// store, and `cfg` attributes are applied to all the items that are
+/// generated for the store. The `allow`, `cfg_attr`, `deny`, `derive`,
+/// `expect`, `must_use` and `warn` attributes are put on the store struct,
+/// any other attribute is an error.
+///
+/// With the `mrt` feature of this crate enabled, the store gets an
+/// `export_mrt_table_dump_v2` method, that writes the store as an MRT
+/// TABLE_DUMP_V2 table dump. This method is only available if the meta-data
+/// type implements `MrtMeta`.
+///
+/// With the `snapshot` feature of this crate enabled, the store also gets
+/// `write_snapshot` and `read_snapshot` methods, that write the store to and
+/// restore it from a versioned binary format. These methods are only
+/// available if the meta-data type implements `SnapshotMeta`.
+///
+/// The macro takes these named arguments, that can each be omitted:
+///
+/// * `v4`: the stride sizes for the IPv4 tree, by default
+///   `[5, 5, 4, 3, 3, 3, 3, 3, 3]`.
+/// * `v6`: the stride sizes for the IPv6 tree, by default `[4; 32]`.
+/// * `bucket_map`: how the prefixes and nodes of each length are spread over
+///   the levels of buckets in the storage, by default `uniform(4)`. This is
+///   one of `uniform(N)`, with N bits per level, from 1 up to and including
+///   16, `legacy`, with the tables that have 12 bits in the first level and
+///   up to 24 in the second, or a table with an array of the end bits of
+///   the levels for each prefix length, e.g. `[12, 24, 28]` for length 28.
+///   A tuple sets the bucket maps for IPv4 and IPv6 separately, e.g.
+///   `bucket_map = (legacy, uniform(8))`.
+/// * `crate`: the path to the rotonda-store crate as a string, by default
+///   `"::rotonda_store"`.
+///
+/// The stride sizes for IPv4 and IPv6 can also be passed in as a tuple,
+/// e.g. `#[create_store(([4; 8], [4; 32]))]`, optionally followed by the
+/// `crate` argument.
+///
+/// All the names in the generated code are fully qualified with the
+/// `crate` path, so the store does not depend on what is imported in the
+/// module it is created in. A crate that re-exports rotonda-store can use
+/// e.g. `crate = "my_crate::rotonda_store"`, and rotonda-store itself uses
+/// `crate = "crate"`.
+///
+/// A store for only one address family can be created by setting the stride
+/// sizes for the other address family to `None`, e.g.
+/// `#[create_store(v4 = [4; 8], v6 = None)]`. Such a store does not have a
+/// tree for the other address family at all. Its methods that return a
+/// `Result` return a `PrefixStoreError::UnsupportedAddressFamily` error for
+/// prefixes or `multi_uniq_id`s of the other address family, its query
+/// methods return an empty result, its iterators are empty, and its counters
+/// are zero for the other address family.
+///
+/// The stride sizes can be written as an array, e.g. `[5, 5, 4, 3]`, as a
+/// repeat expression, e.g. `[4; 8]`, or as the name or path of a const
+/// that was defined with the [`strides!`] macro in the same crate:
+///
+/// ```ignore
+/// strides! {
+///     const IP4_STRIDE_ARRAY = [4; 8];
+///     const IP6_STRIDE_ARRAY = [4; 32];
+/// }
+///
+/// #[create_store(v4 = IP4_STRIDE_ARRAY, v6 = IP6_STRIDE_ARRAY)]
+/// struct NuStorage;
+/// ```
+///
+/// The stride-sizes can be any of \[3,4,5\], or any of
+/// \[1,2,3,4,5,6,7,8\] with the `extra-strides` feature of this crate,
+/// which needs the node types for these sizes in `rotonda-store`. They
+/// should add up to the total number of bits in the address family (32 for
+/// IPv4 and 128 for IPv6). Stride sizes in the array will be repeated if the sum
 /// of them falls short of the total number of bits for the address
-/// family.
+/// family, so `[4]` for IPv4 is the same as `[4, 4, 4, 4, 4, 4, 4, 4]`.
+/// The repeated strides should end exactly at the number of bits for
+/// the address family, e.g. `[5]` cannot be used for IPv4.
+///
+/// This is checked at compile time: an array with an unsupported stride
+/// size, an empty array, an array with more than 42 strides (after
+/// repetition), or an array that does not add up will fail to compile. The
+/// limit of 42 strides means that the strides for IPv6 have to be more than
+/// 3 bits wide on average, so e.g. `[2; 64]` can not be used for IPv6.
 ///
 /// # Example
-/// ```
+/// ```ignore
 /// use rotonda_store::prelude::*;
 /// use rotonda_store::prelude::multi::*;
 /// use rotonda_store::meta_examples::PrefixAs;
 ///
 /// // The default stride sizes for IPv4, IPv6, resp.
-/// #[create_store((
-///     [5, 5, 4, 3, 3, 3, 3, 3, 3, 3],
-///     [4, 4, 4, 4, 4, 4, 4, 4, 4, 4, 4, 4, 4, 4, 4, 4, 4, 4, 4, 4, 4,
+/// #[create_store(
+///     v4 = [5, 5, 4, 3, 3, 3, 3, 3, 3],
+///     v6 = [4, 4, 4, 4, 4, 4, 4, 4, 4, 4, 4, 4, 4, 4, 4, 4, 4, 4, 4, 4, 4,
 ///     4, 4, 4, 4, 4, 4, 4, 4, 4, 4, 4]
-/// ))]
+/// )]
 /// struct NuStore;
 ///
 /// let store = Arc::new(NuStore::<PrefixAs>::new().unwrap());
 /// ```
 #[proc_macro_attribute]
 pub fn create_store(attr: TokenStream, item: TokenStream) -> TokenStream {
-    let input = parse_macro_input!(item as syn::ItemStruct);
+    expand_create_store(attr.into(), item.into()).into()
+}
+
+fn expand_create_store(
+    attr: proc_macro2::TokenStream,
+    item: proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    let input = match syn::parse2::<syn::ItemStruct>(item) {
+        Ok(input) => input,
+        Err(err) => return err.to_compile_error(),
+    };
+    let input = match crate::item::StoreItem::try_from(input) {
+        Ok(input) => input,
+        Err(err) => return err.to_compile_error(),
+    };
     let store_name = &input.ident;
+    let store_vis = &input.vis;
+    let store_attrs = &input.attrs;
+    let cfgs = &input.cfgs;
+    let cfgs = quote! { #( #cfgs )* };
+
+    // The doc comments of the user go in front of the generated docs,
+    // separated by an empty line.
+    let docs = &input.docs;
+    let docs_sep = if docs.is_empty() {
+        quote! {}
+    } else {
+        quote! { #[doc = ""] }
+    };
 
-    let attr = parse_macro_input!(attr as syn::ExprTuple);
-    let attrs = attr.elems.iter().collect::<Vec<_>>();
-    let strides4 = attrs[0].clone();
-    let strides6 = attrs[1].clone();
+    let args = match syn::parse2::<crate::args::StoreArgs>(attr) {
+        Ok(args) => args,
+        Err(err) => return err.to_compile_error(),
+    };
+    let krate = &args.krate;
+    let crate_arg = crate::args::crate_arg(krate);
     let strides4_name = format_ident!("{}IPv4", store_name);
+    let observer_name = format_ident!("{}BestPathObserver", store_name);
+    let changes_name = format_ident!("{}StatusChanges", store_name);
+    // The observer is called with the prefix, and the old and new best and
+    // backup paths.
+    let observer_fn = quote! {
+        dyn Fn(
+            #krate::prelude::Prefix,
+            Option<u32>,
+            Option<u32>,
+            Option<u32>,
+            Option<u32>
+        ) + Send + Sync
+    };
     let strides6_name = format_ident!("{}IPv6", store_name);
 
-    let create_strides = quote! {
-        use ::std::marker::PhantomData;
-        use ::inetnum::addr::Prefix;
+    // The address families the store has a tree for. For each of these we
+    // have the name of the field in the store (`v4`), the name of the
+    // `IpAddr` variant (`V4`), the address family as handed to
+    // `stride_sizes` (`IPv4`) and as a type (`rotonda_store::prelude::IPv4`),
+    // the name of the tree type and its stride sizes. Methods that work on
+    // any address family have a match arm, or a part in an iterator chain,
+    // for each of these, and a fallback for the address families that the
+    // store does not have a tree for.
+    let mut fields = vec![];
+    let mut variants = vec![];
+    let mut ip_afs = vec![];
+    let mut af_types = vec![];
+    let mut trees = vec![];
+    let mut strides = vec![];
+    let mut bucket_map_args = vec![];
+    let mut absent_variants = vec![];
+
+    let (bucket_map4, bucket_map6) = match &args.bucket_map {
+        Some((v4, v6)) => (Some(v4), Some(v6)),
+        None => (None, None),
+    };
+
+    for (field, variant, ip_af, tree, family_strides, bucket_map) in [
+        ("v4", "V4", "IPv4", &strides4_name, &args.v4, bucket_map4),
+        ("v6", "V6", "IPv6", &strides6_name, &args.v6, bucket_map6),
+    ] {
+        match family_strides {
+            Some(family_strides) => {
+                fields.push(format_ident!("{}", field));
+                variants.push(format_ident!("{}", variant));
+                let ip_af = format_ident!("{}", ip_af);
+                af_types.push(quote! { #krate::prelude::#ip_af });
+                ip_afs.push(ip_af);
+                trees.push(tree);
+                strides.push(family_strides);
+                bucket_map_args.push(bucket_map.map(|map| quote! { , bucket_map = #map }));
+            }
+            None => absent_variants.push(format_ident!("{}", variant)),
+        }
+    }
+
+    // The bodies of the methods that are specific to one address family are
+    // generated by a closure that takes the name of the field for the tree
+    // in the store and the address family type. These return that body for
+    // IPv4 and IPv6, respectively, or the `absent` tokens if the store does
+    // not have a tree for the address family.
+    type BodyFn<'a> = &'a dyn Fn(&syn::Ident, &syn::Ident) -> proc_macro2::TokenStream;
+    let v4_body = |body: BodyFn, absent: proc_macro2::TokenStream| match args.v4 {
+        Some(_) => body(&format_ident!("v4"), &format_ident!("IPv4")),
+        None => absent,
+    };
+    let v6_body = |body: BodyFn, absent: proc_macro2::TokenStream| match args.v6 {
+        Some(_) => body(&format_ident!("v6"), &format_ident!("IPv6")),
+        None => absent,
+    };
+
+    // Recalculates the best and backup path for the StoredPrefix `p_rec`,
+    // and counts the changes in `report`. The withdrawn records and the records for
+    // the `multi_uniq_id`s in the bitmap of globally withdrawn ones `bmin`
+    // are left out, and for equal paths the lowest `multi_uniq_id` wins.
+    let recalculate = quote! {
+        let ps = unsafe { p_rec.get_path_selections(guard) };
+        let (old_best, old_backup) = (ps.best(), ps.backup());
+        let mut records = p_rec.record_map
+            .iter_records(None, guard)
+            .filter(|r| {
+                matches!(r.status, #krate::prelude::RouteStatus::Active)
+                    && !bmin.contains(r.multi_uniq_id)
+            })
+            .collect::<Vec<_>>();
+        records.sort_by(|a, b| {
+            b.meta.as_orderable(tbi.clone())
+                .cmp(&a.meta.as_orderable(tbi.clone()))
+                .then(a.multi_uniq_id.cmp(&b.multi_uniq_id))
+        });
+        let best = records.first().map(|r| r.multi_uniq_id);
+        let backup = records.get(1).map(|r| r.multi_uniq_id);
+        p_rec.set_path_selections(best, backup, guard)?;
+        self.notify_best_path_change(p_rec.prefix.into_pub(), (old_best, old_backup), (best, backup));
+        report.prefixes_recalculated += 1;
+        if best != old_best {
+            report.best_changed += 1;
+        }
+        if backup != old_backup {
+            report.backup_changed += 1;
+        }
+    };
 
-        #[stride_sizes((IPv4, #strides4))]
-        struct #strides4_name;
+    // The more-specifics iterator of the tree does not return the prefix it
+    // starts from, so the records of the covering prefix, or of the default
+    // route without one, are looked up separately.
+    let iter_records_for_mui = |field: &syn::Ident, ip_af: &syn::Ident| {
+        quote! {
+            let bmin = unsafe {
+                self.#field.store.withdrawn_muis_bmin.load(
+                    ::std::sync::atomic::Ordering::Acquire, guard
+                ).deref()
+            };
+
+            if bmin.contains(mui) && !include_withdrawn {
+                None
+            } else {
+                let start = within.map_or(
+                    #krate::prelude::multi::PrefixId::<#krate::prelude::#ip_af>::new(0, 0),
+                    #krate::prelude::multi::PrefixId::from,
+                );
+                let covering = self.#field.store
+                    .non_recursive_retrieve_prefix(start)
+                    .0
+                    .and_then(|p_rec| {
+                    // Like in the more-specifics iterator, the records
+                    // for a globally withdrawn mui are withdrawn.
+                    let records = p_rec
+                        .record_map
+                        .iter_records(Some(mui), guard)
+                        .map(|r| {
+                            let mut r = r.clone();
+                            if bmin.contains(mui) {
+                                r.status = #krate::prelude::RouteStatus::Withdrawn;
+                            }
+                            r
+                        })
+                        .filter(|r| include_withdrawn || !matches!(
+                            r.status,
+                            #krate::prelude::RouteStatus::Withdrawn
+                        ))
+                        .collect::<Vec<_>>();
+                    (!records.is_empty()).then(|| (start.into_pub(), records))
+                });
+                Some(
+                    covering.into_iter().chain(
+                        self.#field.store.more_specific_prefix_iter_from(
+                            start,
+                            Some(mui),
+                            include_withdrawn,
+                            guard
+                        )
+                    ).map(|p| #krate::prelude::PrefixRecord::from(p))
+                )
+            }.into_iter().flatten()
+        }
+    };
+    let iter_records_for_mui_v4 = v4_body(&iter_records_for_mui, quote! { ::std::iter::empty() });
+    let iter_records_for_mui_v6 = v6_body(&iter_records_for_mui, quote! { ::std::iter::empty() });
+
+    // A record has changed after `ltime` if it was inserted or replaced, if
+    // its local status was changed, or if the global status of its
+    // `multi_uniq_id` was changed after `ltime`, and it is returned with the
+    // last of these logical times. The changes of the statuses are copied
+    // out of their locks before the prefixes are walked.
+    let changes_since = |field: &syn::Ident, _: &syn::Ident| {
+        quote! {
+            let bmin = unsafe {
+                self.#field.store.withdrawn_muis_bmin.load(
+                    ::std::sync::atomic::Ordering::Acquire, guard
+                ).deref()
+            };
+            let records_changed = #changes_name::since(&self.status_changes.records, ltime);
+            let muis_changed = #changes_name::since(&self.status_changes.#field, ltime);
+
+            self.#field.store.stored_prefixes_iter(guard).filter_map(move |p_rec| {
+                let prefix = p_rec.prefix.into_pub();
+                let records = p_rec
+                    .record_map
+                    .iter_records(None, guard)
+                    .filter_map(|r| {
+                        let changed = [
+                            records_changed.get(&(prefix, r.multi_uniq_id)),
+                            muis_changed.get(&r.multi_uniq_id),
+                        ]
+                        .into_iter()
+                        .flatten()
+                        .fold(r.ltime, |ltime, changed| ltime.max(*changed));
+                        (changed > ltime).then(|| {
+                            let mut r = r.clone();
+                            r.ltime = changed;
+                            if bmin.contains(r.multi_uniq_id) {
+                                r.status = #krate::prelude::RouteStatus::Withdrawn;
+                            }
+                            r
+                        })
+                    })
+                    .collect::<Vec<_>>();
+                (!records.is_empty())
+                    .then(|| #krate::prelude::PrefixRecord::from((prefix, records)))
+            })
+        }
+    };
+    let changes_since_v4 = v4_body(&changes_since, quote! { ::std::iter::empty() });
+    let changes_since_v6 = v6_body(&changes_since, quote! { ::std::iter::empty() });
 
-        #[stride_sizes((IPv6, #strides6))]
-        struct #strides6_name;
+    let prefixes_iter = |field: &syn::Ident, _: &syn::Ident| {
+        quote! {
+            self.#field.store.prefixes_iter()
+                .map(|p| #krate::prelude::PrefixRecord::from(p))
+        }
+    };
+    let prefixes_iter_v4 = v4_body(&prefixes_iter, quote! { ::std::iter::empty() });
+    let prefixes_iter_v6 = v6_body(&prefixes_iter, quote! { ::std::iter::empty() });
+
+    // The prefixes of the tree in address-then-length order, from the
+    // prefix `from` on, if it is set. A pre-order walk of the tree, with the
+    // prefix before its more-specifics and the lower half before the upper
+    // half, has that order. The walk keeps a stack of the (address, length)
+    // of the prefixes whose subtrees are still to be walked. A subtree with
+    // at most BATCH more-specifics is taken from the more-specifics iterator
+    // of the tree in one go, and sorted, a larger one is split into its two
+    // halves. Subtrees that end before `from` are skipped, so the walk
+    // descends along the path to `from` instead of sorting everything before
+    // it.
+    let prefixes_ordered = |field: &syn::Ident, ip_af: &syn::Ident| {
+        let (af, addr_ty, variant, bits) = if ip_af == "IPv4" {
+            (quote! { u32 }, quote! { Ipv4Addr }, quote! { V4 }, 32_u8)
+        } else {
+            (quote! { u128 }, quote! { Ipv6Addr }, quote! { V6 }, 128_u8)
+        };
+        quote! {
+            const BATCH: usize = 64;
+
+            let from = from
+                .and_then(|p| match p.addr() {
+                    ::std::net::IpAddr::#variant(addr) => Some((#af::from(addr), p.len())),
+                    _ => None,
+                })
+                .unwrap_or((0, 0));
+            let from_key = (::std::net::IpAddr::from(::std::net::#addr_ty::from(from.0)), from.1);
+            let options = #krate::prelude::MatchOptions {
+                match_type: #krate::prelude::MatchType::ExactMatch,
+                include_withdrawn: true,
+                include_less_specifics: false,
+                include_more_specifics: false,
+                mui: None,
+            };
+
+            let mut stack: Vec<(#af, u8)> = vec![(0, 0)];
+            let mut pending = ::std::collections::VecDeque::new();
+            ::std::iter::from_fn(move || loop {
+                if let Some(p_rec) = pending.pop_front() {
+                    return Some(p_rec);
+                }
+
+                let (addr, len) = stack.pop()?;
+                let last = addr | #af::MAX.checked_shr(u32::from(len)).unwrap_or(0);
+                if last < from.0 {
+                    continue;
+                }
+
+                let guard = &#krate::prelude::multi::epoch::pin();
+                let id = #krate::prelude::multi::PrefixId::<#krate::prelude::#ip_af>::new(addr, len);
+                if (addr, len) >= from {
+                    let res = self.#field.match_prefix_by_store_direct(id, &options, None, guard);
+                    if let (#krate::prelude::MatchType::ExactMatch, Some(prefix)) =
+                        (res.match_type, res.prefix)
+                    {
+                        pending.push_back(#krate::prelude::PrefixRecord::from((prefix, res.prefix_meta)));
+                    }
+                }
+
+                let more_specifics = self.#field.store
+                    .more_specific_prefix_iter_from(id, None, true, guard)
+                    .take(BATCH + 1)
+                    .collect::<Vec<_>>();
+                if more_specifics.len() <= BATCH {
+                    let mut more_specifics = more_specifics
+                        .into_iter()
+                        .filter(|(prefix, _)| (prefix.addr(), prefix.len()) >= from_key)
+                        .collect::<Vec<_>>();
+                    more_specifics.sort_unstable_by_key(|(prefix, _)| (prefix.addr(), prefix.len()));
+                    pending.extend(more_specifics.into_iter().map(#krate::prelude::PrefixRecord::from));
+                } else if len < #bits {
+                    stack.push((addr | (1 << (#bits - 1 - len)), len + 1));
+                    stack.push((addr, len + 1));
+                }
+            })
+        }
+    };
+    let prefixes_ordered_v4 = v4_body(&prefixes_ordered, quote! { ::std::iter::empty() });
+    let prefixes_ordered_v6 = v6_body(&prefixes_ordered, quote! { ::std::iter::empty() });
+
+    let mark_mui_as_active = |field: &syn::Ident, _: &syn::Ident| {
+        quote! {
+            let guard = &#krate::prelude::multi::epoch::pin();
+
+            self.#field.store.mark_mui_as_active(
+                mui,
+                &guard
+            ).map(|()| self.status_changes.mui_changed(&self.status_changes.#field, mui))
+        }
+    };
+    let unsupported =
+        quote! { Err(#krate::prelude::multi::PrefixStoreError::UnsupportedAddressFamily) };
+    let mark_mui_as_active_v4 = v4_body(&mark_mui_as_active, unsupported.clone());
+    let mark_mui_as_active_v6 = v6_body(&mark_mui_as_active, unsupported.clone());
+
+    let mark_mui_as_withdrawn = |field: &syn::Ident, _: &syn::Ident| {
+        quote! {
+            let guard = &#krate::prelude::multi::epoch::pin();
+
+            self.#field.store.mark_mui_as_withdrawn(
+                mui,
+                &guard
+            ).map(|()| self.status_changes.mui_changed(&self.status_changes.#field, mui))
+        }
+    };
+    let mark_mui_as_withdrawn_v4 = v4_body(&mark_mui_as_withdrawn, unsupported.clone());
+    let mark_mui_as_withdrawn_v6 = v6_body(&mark_mui_as_withdrawn, unsupported.clone());
+
+    // The results of activating a `multi_uniq_id` for each address family,
+    // for the `MuiReport`.
+    let mark_mui_as_active_report = |field: &syn::Ident, _: &syn::Ident| {
+        quote! {
+            Some(
+                self.#field.store.mark_mui_as_active(mui, guard)
+                    .map(|()| self.status_changes.mui_changed(&self.status_changes.#field, mui))
+            )
+        }
+    };
+    let mark_mui_as_active_report_v4 = v4_body(&mark_mui_as_active_report, quote! { None });
+    let mark_mui_as_active_report_v6 = v6_body(&mark_mui_as_active_report, quote! { None });
+
+    let mui_is_withdrawn = |field: &syn::Ident, _: &syn::Ident| {
+        quote! {
+            let guard = &#krate::prelude::multi::epoch::pin();
+
+            self.#field.store.mui_is_withdrawn(mui, guard)
+        }
+    };
+    let mui_is_withdrawn_v4 = v4_body(&mui_is_withdrawn, quote! { false });
+    let mui_is_withdrawn_v6 = v6_body(&mui_is_withdrawn, quote! { false });
+
+    let mui_status = |field: &syn::Ident, _: &syn::Ident| {
+        quote! {
+            Some(if self.#field.store.mui_is_withdrawn(mui, guard) {
+                #krate::prelude::RouteStatus::Withdrawn
+            } else {
+                #krate::prelude::RouteStatus::Active
+            })
+        }
+    };
+    let mui_status_v4 = v4_body(&mui_status, quote! { None });
+    let mui_status_v6 = v6_body(&mui_status, quote! { None });
+
+    let prefixes_count = |field: &syn::Ident, _: &syn::Ident| {
+        quote! { self.#field.store.get_prefixes_count() }
+    };
+    let prefixes_v4_count = v4_body(&prefixes_count, quote! { 0 });
+    let prefixes_v6_count = v6_body(&prefixes_count, quote! { 0 });
+
+    let prefixes_count_for_len = |field: &syn::Ident, _: &syn::Ident| {
+        quote! { self.#field.store.get_prefixes_count_for_len(len) }
+    };
+    let prefixes_v4_count_for_len = v4_body(&prefixes_count_for_len, quote! { 0 });
+    let prefixes_v6_count_for_len = v6_body(&prefixes_count_for_len, quote! { 0 });
+
+    let nodes_count = |field: &syn::Ident, _: &syn::Ident| {
+        quote! { self.#field.store.get_nodes_count() }
+    };
+    let nodes_v4_count = v4_body(&nodes_count, quote! { 0 });
+    let nodes_v6_count = v6_body(&nodes_count, quote! { 0 });
+
+    let stats = |field: &syn::Ident, _: &syn::Ident| {
+        quote! { self.#field.store.counters.get_prefix_stats() }
+    };
+    let stats_v4 = v4_body(&stats, quote! { Default::default() });
+    let stats_v6 = v6_body(&stats, quote! { Default::default() });
+
+    let create_strides = quote! {
+        #(
+            #cfgs
+            #[#krate::prelude::multi::stride_sizes(#ip_afs, #strides, #crate_arg #bucket_map_args)]
+            struct #trees;
+        )*
     };
 
     let store = quote! {
+        #( #docs )*
+        #docs_sep
         /// A concurrently read/writable, lock-free Prefix Store, for use in a
         /// multi-threaded context.
         ///
@@ -413,15 +992,129 @@ pub fn create_store(attr: TokenStream, item: TokenStream) -> TokenStream {
         ///
         /// This way the store can hold RIBs for multiple peers in one
         /// data-structure.
-        pub struct #store_name<
-            M: Meta
+        #cfgs
+        #( #store_attrs )*
+        #store_vis struct #store_name<
+            M: #krate::prelude::Meta
         > {
-            v4: #strides4_name<M>,
-            v6: #strides6_name<M>,
+            #( #fields: #trees<M>, )*
+            best_path_observer: #observer_name,
+            status_changes: #changes_name,
+        }
+
+        // The observer set with `set_best_path_observer`, if any. It is set
+        // through a shared reference, so that it can be set on a store that
+        // is shared between threads, and it is cloned out of the lock
+        // before it is called, so that it can set another observer.
+        #cfgs
+        #[derive(Default)]
+        struct #observer_name(
+            ::std::sync::RwLock<Option<::std::sync::Arc<#observer_fn>>>
+        );
+
+        #cfgs
+        impl #observer_name {
+            fn get(&self) -> Option<::std::sync::Arc<#observer_fn>> {
+                self.0.read().unwrap_or_else(::std::sync::PoisonError::into_inner).clone()
+            }
+
+            fn set(&self, observer: Option<::std::sync::Arc<#observer_fn>>) {
+                *self.0.write().unwrap_or_else(::std::sync::PoisonError::into_inner) = observer;
+            }
+        }
+
+        // A clone shares the observer with the original.
+        #cfgs
+        impl Clone for #observer_name {
+            fn clone(&self) -> Self {
+                Self(::std::sync::RwLock::new(self.get()))
+            }
+        }
+
+        #cfgs
+        impl ::std::fmt::Debug for #observer_name {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                f.write_str(if self.get().is_some() { "Some(..)" } else { "None" })
+            }
+        }
+
+        // The logical times of the status changes, for `changes_since`. A
+        // status change does not touch the logical time of the records, so
+        // it gets a logical time one above the highest one in the store,
+        // which is kept here for the last change of the local status of
+        // each `(prefix, multi_uniq_id)`, and of the global status of each
+        // `multi_uniq_id` per address family.
+        #cfgs
+        #[derive(Debug, Default)]
+        struct #changes_name {
+            ltime: ::std::sync::atomic::AtomicU64,
+            records: ::std::sync::Mutex<
+                ::std::collections::HashMap<(#krate::prelude::Prefix, u32), u64>
+            >,
+            #( #fields: ::std::sync::Mutex<::std::collections::HashMap<u32, u64>>, )*
+        }
+
+        #cfgs
+        impl #changes_name {
+            fn lock<T>(changes: &::std::sync::Mutex<T>) -> ::std::sync::MutexGuard<'_, T> {
+                changes.lock().unwrap_or_else(::std::sync::PoisonError::into_inner)
+            }
+
+            // Raises the highest logical time in the store to `ltime`.
+            fn seen(&self, ltime: u64) {
+                self.ltime.fetch_max(ltime, ::std::sync::atomic::Ordering::AcqRel);
+            }
+
+            // Returns the logical time for a new status change.
+            fn next(&self) -> u64 {
+                self.ltime.fetch_add(1, ::std::sync::atomic::Ordering::AcqRel) + 1
+            }
+
+            fn record_changed(&self, prefix: #krate::prelude::Prefix, mui: u32) {
+                let ltime = self.next();
+                Self::lock(&self.records).insert((prefix, mui), ltime);
+            }
+
+            fn mui_changed(
+                &self,
+                muis: &::std::sync::Mutex<::std::collections::HashMap<u32, u64>>,
+                mui: u32
+            ) {
+                let ltime = self.next();
+                Self::lock(muis).insert(mui, ltime);
+            }
+
+            // Returns the changes in `changes` after the logical time
+            // `ltime`.
+            fn since<K: Clone + Eq + ::std::hash::Hash>(
+                changes: &::std::sync::Mutex<::std::collections::HashMap<K, u64>>,
+                ltime: u64
+            ) -> ::std::collections::HashMap<K, u64> {
+                Self::lock(changes)
+                    .iter()
+                    .filter(|(_, changed)| **changed > ltime)
+                    .map(|(key, changed)| (key.clone(), *changed))
+                    .collect()
+            }
+        }
+
+        // A clone has the status changes of the original up to the clone.
+        #cfgs
+        impl Clone for #changes_name {
+            fn clone(&self) -> Self {
+                Self {
+                    ltime: ::std::sync::atomic::AtomicU64::new(
+                        self.ltime.load(::std::sync::atomic::Ordering::Acquire)
+                    ),
+                    records: ::std::sync::Mutex::new(Self::lock(&self.records).clone()),
+                    #( #fields: ::std::sync::Mutex::new(Self::lock(&self.#fields).clone()), )*
+                }
+            }
         }
 
+        #cfgs
         impl<
-                M: Meta
+                M: #krate::prelude::Meta
             > Default for #store_name<M>
         {
             fn default() -> Self {
@@ -429,11 +1122,14 @@ pub fn create_store(attr: TokenStream, item: TokenStream) -> TokenStream {
             }
         }
 
+        #cfgs
         impl<
-                M: Meta
+                M: #krate::prelude::Meta
             > #store_name<M>
         {
-            /// Creates a new empty store with a tree for IPv4 and on for IPv6.
+            /// Creates a new empty store with a tree for IPv4 and on for IPv6,
+            /// or a tree for only one of them, if the store was created
+            /// for only one address family.
             ///
             /// The store will be created with the default stride sizes. After
             /// creation you can wrap the store in an Arc<_> and `clone()` that
@@ -483,15 +1179,79 @@ pub fn create_store(attr: TokenStream, item: TokenStream) -> TokenStream {
             ///          })
             ///      }).map(|t| t.join()).collect();
             /// ```
-            pub fn new() -> Result<Self, Box<dyn std::error::Error>> {
+            pub fn new() -> Result<Self, Box<dyn ::std::error::Error>> {
                 Ok(Self {
-                    v4: #strides4_name::new()?,
-                    v6: #strides6_name::new()?,
+                    #( #fields: #trees::new()?, )*
+                    best_path_observer: #observer_name::default(),
+                    status_changes: #changes_name::default(),
                 })
             }
+
+            /// Sets the observer that is called whenever the stored best
+            /// or backup path for a prefix changes, replacing the previous
+            /// one, if any.
+            ///
+            /// The observer is called with the prefix, the old and the new
+            /// best path, and the old and the new backup path, as
+            /// `multi_uniq_id`s, in that order. It is called by
+            /// `calculate_and_store_best_and_backup_path`, `insert` and
+            /// `insert_batch` with `update_path_selections`, and the
+            /// `recalculate_*` methods, on the thread that made the change,
+            /// so it should return quickly, e.g. by sending the change over
+            /// a channel:
+            ///
+            /// ```ignore
+            /// let (tx, rx) = std::sync::mpsc::channel();
+            /// store.set_best_path_observer(
+            ///     move |prefix, old_best, new_best, _old_backup, _new_backup| {
+            ///         let _ = tx.send((prefix, old_best, new_best));
+            ///     }
+            /// );
+            /// ```
+            ///
+            /// The observer can be set and cleared on a store that is shared
+            /// between threads. A change that is made while the observer is
+            /// replaced is reported to either the old or the new one.
+            ///
+            /// For `insert`, the old path selections are read before the
+            /// insert, so a concurrent change of the path selections for
+            /// the same prefix may be reported as part of the change.
+            pub fn set_best_path_observer(
+                &self,
+                observer: impl Fn(
+                    #krate::prelude::Prefix,
+                    Option<u32>,
+                    Option<u32>,
+                    Option<u32>,
+                    Option<u32>
+                ) + Send + Sync + 'static
+            ) {
+                self.best_path_observer.set(Some(::std::sync::Arc::new(observer)));
+            }
+
+            /// Removes the observer set with `set_best_path_observer`.
+            pub fn clear_best_path_observer(&self) {
+                self.best_path_observer.set(None);
+            }
+
+            // Calls the best path observer, if any, if the `(best, backup)`
+            // path selections for `prefix` changed from `old` to `new`.
+            fn notify_best_path_change(
+                &self,
+                prefix: #krate::prelude::Prefix,
+                old: (Option<u32>, Option<u32>),
+                new: (Option<u32>, Option<u32>)
+            ) {
+                if old != new {
+                    if let Some(observer) = self.best_path_observer.get() {
+                        observer(prefix, old.0, new.0, old.1, new.1);
+                    }
+                }
+            }
         }
 
-        impl<'a, M: Meta,
+        #cfgs
+        impl<'a, M: #krate::prelude::Meta,
             > #store_name<M>
         {
             /// Search for and return one or more prefixes that match the given
@@ -595,32 +1355,34 @@ pub fn create_store(attr: TokenStream, item: TokenStream) -> TokenStream {
             /// ```
             pub fn match_prefix(
                 &'a self,
-                search_pfx: &Prefix,
-                options: &MatchOptions,
-                guard: &'a Guard,
-            ) -> QueryResult<M> {
+                search_pfx: &#krate::prelude::Prefix,
+                options: &#krate::prelude::MatchOptions,
+                guard: &'a #krate::prelude::multi::Guard,
+            ) -> #krate::prelude::QueryResult<M> {
 
                 match search_pfx.addr() {
-                    std::net::IpAddr::V4(addr) => {
-                        self.v4.match_prefix_by_store_direct(
-                            PrefixId::<IPv4>::new(
-                                addr.into(),
-                                search_pfx.len(),
-                            ),
-                            options,
-                            options.mui,
-                            guard
-                        )
-                    },
-                    std::net::IpAddr::V6(addr) => self.v6.match_prefix_by_store_direct(
-                        PrefixId::<IPv6>::new(
-                            addr.into(),
-                            search_pfx.len(),
-                        ),
-                        options,
-                        options.mui,
-                        guard
-                    ),
+                    #(
+                        ::std::net::IpAddr::#variants(addr) => {
+                            self.#fields.match_prefix_by_store_direct(
+                                #krate::prelude::multi::PrefixId::<#af_types>::new(
+                                    addr.into(),
+                                    search_pfx.len(),
+                                ),
+                                options,
+                                options.mui,
+                                guard
+                            )
+                        },
+                    )*
+                    #(
+                        ::std::net::IpAddr::#absent_variants(_) => #krate::prelude::QueryResult {
+                            match_type: #krate::prelude::MatchType::EmptyMatch,
+                            prefix: None,
+                            prefix_meta: vec![],
+                            less_specifics: None,
+                            more_specifics: None,
+                        },
+                    )*
                 }
             }
 
@@ -634,45 +1396,215 @@ pub fn create_store(attr: TokenStream, item: TokenStream) -> TokenStream {
             /// `PrefixError::StoreNotReadyError` should never happen: it
             /// would indicate an internal inconsistency in the store.
             pub fn best_path(&'a self,
-                search_pfx: &Prefix,
-                guard: &Guard
-            ) -> Option<Result<Record<M>, PrefixStoreError>> {
+                search_pfx: &#krate::prelude::Prefix,
+                guard: &#krate::prelude::multi::Guard
+            ) -> Option<Result<#krate::prelude::Record<M>, #krate::prelude::multi::PrefixStoreError>> {
 
                 match search_pfx.addr() {
-                    std::net::IpAddr::V4(addr) => self.v4.store
-                        .non_recursive_retrieve_prefix(
-                            PrefixId::<IPv4>::new(
-                                addr.into(),
-                                search_pfx.len(),
-                            ),
-                        )
-                        .0
-                        .map(|p_rec| unsafe { p_rec
-                            .get_path_selections(guard).best()
-                            .map_or_else(
-                                || Err(PrefixStoreError::BestPathNotFound),
-                                |mui| p_rec.record_map
-                                    .get_record_for_active_mui(mui)
-                                    .ok_or(PrefixStoreError::StoreNotReadyError)
+                    #(
+                        ::std::net::IpAddr::#variants(addr) => self.#fields.store
+                            .non_recursive_retrieve_prefix(
+                                #krate::prelude::multi::PrefixId::<#af_types>::new(
+                                    addr.into(),
+                                    search_pfx.len(),
+                                ),
                             )
-                        }),
-                    std::net::IpAddr::V6(addr) => self.v6.store
-                        .non_recursive_retrieve_prefix(
-                            PrefixId::<IPv6>::new(
-                                addr.into(),
-                                search_pfx.len(),
-                            ),
-                        )
-                        .0
-                        .map(|p_rec| unsafe { p_rec
-                            .get_path_selections(guard).best()
-                            .map_or_else(
-                                || Err(PrefixStoreError::BestPathNotFound),
-                                |mui| p_rec.record_map
+                            .0
+                            .map(|p_rec| unsafe { p_rec
+                                .get_path_selections(guard).best()
+                                .map_or_else(
+                                    || Err(#krate::prelude::multi::PrefixStoreError::BestPathNotFound),
+                                    |mui| p_rec.record_map
+                                        .get_record_for_active_mui(mui)
+                                        .ok_or(#krate::prelude::multi::PrefixStoreError::StoreNotReadyError)
+                                )
+                            }),
+                    )*
+                    #(
+                        ::std::net::IpAddr::#absent_variants(_) => Some(
+                            Err(#krate::prelude::multi::PrefixStoreError::UnsupportedAddressFamily)
+                        ),
+                    )*
+                }
+            }
+
+            /// Return the record that belongs to the pre-calculated and
+            /// stored backup path for a given prefix.
+            ///
+            /// If the Prefix does not exist in the store `None` is returned.
+            /// If the prefix does exist, but no backup path was calculated
+            /// (yet), or there is no backup path because there is only one
+            /// path for the prefix, a `PrefixStoreError::BestPathNotFound`
+            /// error will be returned. A returned result of
+            /// `PrefixError::StoreNotReadyError` should never happen: it
+            /// would indicate an internal inconsistency in the store.
+            pub fn backup_path(&'a self,
+                search_pfx: &#krate::prelude::Prefix,
+                guard: &#krate::prelude::multi::Guard
+            ) -> Option<Result<#krate::prelude::Record<M>, #krate::prelude::multi::PrefixStoreError>> {
+
+                match search_pfx.addr() {
+                    #(
+                        ::std::net::IpAddr::#variants(addr) => self.#fields.store
+                            .non_recursive_retrieve_prefix(
+                                #krate::prelude::multi::PrefixId::<#af_types>::new(
+                                    addr.into(),
+                                    search_pfx.len(),
+                                ),
+                            )
+                            .0
+                            .map(|p_rec| unsafe { p_rec
+                                .get_path_selections(guard).backup()
+                                .map_or_else(
+                                    || Err(#krate::prelude::multi::PrefixStoreError::BestPathNotFound),
+                                    |mui| p_rec.record_map
+                                        .get_record_for_active_mui(mui)
+                                        .ok_or(#krate::prelude::multi::PrefixStoreError::StoreNotReadyError)
+                                )
+                            }),
+                    )*
+                    #(
+                        ::std::net::IpAddr::#absent_variants(_) => Some(
+                            Err(#krate::prelude::multi::PrefixStoreError::UnsupportedAddressFamily)
+                        ),
+                    )*
+                }
+            }
+
+            /// Return the records that belong to the pre-calculated and
+            /// stored best and backup paths for a given prefix, from the
+            /// same path selections.
+            ///
+            /// If the Prefix does not exist in the store `None` is returned.
+            /// If the prefix does exist, but no best path was calculated
+            /// (yet), a `PrefixStoreError::BestPathNotFound` error will be
+            /// returned. The backup record is `None` if there is no backup
+            /// path, e.g. because there is only one path for the prefix. A
+            /// returned result of `PrefixError::StoreNotReadyError` should
+            /// never happen: it would indicate an internal inconsistency in
+            /// the store.
+            pub fn best_and_backup_paths(&'a self,
+                search_pfx: &#krate::prelude::Prefix,
+                guard: &#krate::prelude::multi::Guard
+            ) -> Option<Result<
+                (#krate::prelude::Record<M>, Option<#krate::prelude::Record<M>>),
+                #krate::prelude::multi::PrefixStoreError
+            >> {
+
+                match search_pfx.addr() {
+                    #(
+                        ::std::net::IpAddr::#variants(addr) => self.#fields.store
+                            .non_recursive_retrieve_prefix(
+                                #krate::prelude::multi::PrefixId::<#af_types>::new(
+                                    addr.into(),
+                                    search_pfx.len(),
+                                ),
+                            )
+                            .0
+                            .map(|p_rec| {
+                                let ps = unsafe { p_rec.get_path_selections(guard) };
+                                let record = |mui| p_rec.record_map
                                     .get_record_for_active_mui(mui)
-                                    .ok_or(PrefixStoreError::StoreNotReadyError)
+                                    .ok_or(#krate::prelude::multi::PrefixStoreError::StoreNotReadyError);
+                                let best = ps.best()
+                                    .ok_or(#krate::prelude::multi::PrefixStoreError::BestPathNotFound)
+                                    .and_then(record)?;
+                                Ok((best, ps.backup().map(record).transpose()?))
+                            }),
+                    )*
+                    #(
+                        ::std::net::IpAddr::#absent_variants(_) => Some(
+                            Err(#krate::prelude::multi::PrefixStoreError::UnsupportedAddressFamily)
+                        ),
+                    )*
+                }
+            }
+
+            /// Returns the records for the prefix in the store, if the prefix
+            /// is in the store. If `mui` is set, only the record for that
+            /// `multi_uniq_id` is returned.
+            ///
+            /// This is an exact match lookup that goes directly to the
+            /// prefix, without the bookkeeping for less- and more-specifics
+            /// that `match_prefix` does. The records are borrowed from the
+            /// store for as long as the `guard` lives. The records have
+            /// their local status, the global status of a `multi_uniq_id`
+            /// is not applied to them.
+            ///
+            /// # Example
+            /// ```
+            /// use std::net::Ipv4Addr;
+            ///
+            /// use rotonda_store::prelude::*;
+            /// use rotonda_store::prelude::multi::*;
+            /// use rotonda_store::meta_examples::PrefixAs;
+            ///
+            /// let store = MultiThreadedStore::<PrefixAs>::new().unwrap();
+            /// let guard = &epoch::pin();
+            ///
+            /// let pfx = Prefix::new(
+            ///     "185.49.140.0".parse::<Ipv4Addr>().unwrap().into(), 22
+            /// ).unwrap();
+            /// store.insert(
+            ///     &pfx,
+            ///     Record::new(1, 0, RouteStatus::Active, PrefixAs(211321)),
+            ///     None
+            /// ).unwrap();
+            ///
+            /// let records = store.get(&pfx, Some(1), guard).unwrap();
+            /// assert_eq!(records.map(|r| r.multi_uniq_id).collect::<Vec<_>>(), vec![1]);
+            /// assert_eq!(store.get(&pfx, Some(2), guard).unwrap().count(), 0);
+            /// assert!(store.contains(&pfx));
+            /// ```
+            pub fn get(
+                &'a self,
+                search_pfx: &#krate::prelude::Prefix,
+                mui: Option<u32>,
+                guard: &'a #krate::prelude::multi::Guard
+            ) -> Option<impl Iterator<Item=&'a #krate::prelude::Record<M>> + 'a> {
+                let record_map = match search_pfx.addr() {
+                    #(
+                        ::std::net::IpAddr::#variants(addr) => self.#fields.store
+                            .non_recursive_retrieve_prefix(
+                                #krate::prelude::multi::PrefixId::<#af_types>::new(
+                                    addr.into(),
+                                    search_pfx.len(),
+                                ),
                             )
-                        })
+                            .0
+                            .map(|p_rec| &p_rec.record_map),
+                    )*
+                    #(
+                        ::std::net::IpAddr::#absent_variants(_) => None,
+                    )*
+                };
+
+                record_map.map(|record_map| record_map.iter_records(mui, guard))
+            }
+
+            /// Returns whether the prefix is in the store.
+            ///
+            /// Like `get` this is an exact match lookup. A prefix is in the
+            /// store if it has records, whatever their status.
+            pub fn contains(
+                &self,
+                search_pfx: &#krate::prelude::Prefix,
+            ) -> bool {
+                match search_pfx.addr() {
+                    #(
+                        ::std::net::IpAddr::#variants(addr) => self.#fields.store
+                            .non_recursive_retrieve_prefix(
+                                #krate::prelude::multi::PrefixId::<#af_types>::new(
+                                    addr.into(),
+                                    search_pfx.len(),
+                                ),
+                            )
+                            .0
+                            .is_some(),
+                    )*
+                    #(
+                        ::std::net::IpAddr::#absent_variants(_) => false,
+                    )*
                 }
             }
 
@@ -692,69 +1624,150 @@ pub fn create_store(attr: TokenStream, item: TokenStream) -> TokenStream {
             /// An Error result indicates an inconsistency in the store.
             pub fn calculate_and_store_best_and_backup_path(
                 &self,
-                search_pfx: &Prefix,
-                tbi: &<M as Meta>::TBI,
-                guard: &Guard
-            ) -> Result<(Option<u32>, Option<u32>), PrefixStoreError> {
+                search_pfx: &#krate::prelude::Prefix,
+                tbi: &<M as #krate::prelude::Meta>::TBI,
+                guard: &#krate::prelude::multi::Guard
+            ) -> Result<(Option<u32>, Option<u32>), #krate::prelude::multi::PrefixStoreError> {
                 match search_pfx.addr() {
-                    std::net::IpAddr::V4(addr) => self.v4.store
-                        .non_recursive_retrieve_prefix(
-                            PrefixId::<IPv4>::new(
-                                addr.into(),
-                                search_pfx.len(),
-                            ),
-                            // guard
-                        ).0.map_or(
-                            Err(PrefixStoreError::StoreNotReadyError),
-                            |p_rec| p_rec.calculate_and_store_best_backup(
-                                tbi, guard),
-                        ),
-                    std::net::IpAddr::V6(addr) => self.v6.store
-                        .non_recursive_retrieve_prefix(
-                            PrefixId::<IPv6>::new(
-                                addr.into(),
-                                search_pfx.len(),
+                    #(
+                        ::std::net::IpAddr::#variants(addr) => self.#fields.store
+                            .non_recursive_retrieve_prefix(
+                                #krate::prelude::multi::PrefixId::<#af_types>::new(
+                                    addr.into(),
+                                    search_pfx.len(),
+                                ),
+                                // guard
+                            ).0.map_or(
+                                Err(#krate::prelude::multi::PrefixStoreError::StoreNotReadyError),
+                                |p_rec| {
+                                    let ps = unsafe { p_rec.get_path_selections(guard) };
+                                    let old = (ps.best(), ps.backup());
+                                    let new = p_rec.calculate_and_store_best_backup(tbi, guard)?;
+                                    self.notify_best_path_change(*search_pfx, old, new);
+                                    Ok(new)
+                                },
                             ),
-                            // guard
-                        ).0.map_or(
-                            Err(PrefixStoreError::StoreNotReadyError),
-                            |p_rec| p_rec.calculate_and_store_best_backup(
-                                tbi, guard),
+                    )*
+                    #(
+                        ::std::net::IpAddr::#absent_variants(_) => Err(
+                            #krate::prelude::multi::PrefixStoreError::UnsupportedAddressFamily
                         ),
+                    )*
                 }
             }
 
             pub fn is_ps_outdated(
                 &self,
-                search_pfx: &Prefix,
-                guard: &Guard
-            ) -> Result<bool, PrefixStoreError> {
+                search_pfx: &#krate::prelude::Prefix,
+                guard: &#krate::prelude::multi::Guard
+            ) -> Result<bool, #krate::prelude::multi::PrefixStoreError> {
                 match search_pfx.addr() {
-                    std::net::IpAddr::V4(addr) => self.v4.store
-                        .non_recursive_retrieve_prefix(
-                            PrefixId::<IPv4>::new(
-                                addr.into(),
-                                search_pfx.len(),
+                    #(
+                        ::std::net::IpAddr::#variants(addr) => self.#fields.store
+                            .non_recursive_retrieve_prefix(
+                                #krate::prelude::multi::PrefixId::<#af_types>::new(
+                                    addr.into(),
+                                    search_pfx.len(),
+                                ),
+                                // guard
+                            ).0.map_or(
+                                Err(#krate::prelude::multi::PrefixStoreError::StoreNotReadyError),
+                                |p| Ok(p.is_ps_outdated(guard))
                             ),
-                            // guard
-                        ).0.map_or(
-                            Err(PrefixStoreError::StoreNotReadyError),
-                            |p| Ok(p.is_ps_outdated(guard))
+                    )*
+                    #(
+                        ::std::net::IpAddr::#absent_variants(_) => Err(
+                            #krate::prelude::multi::PrefixStoreError::UnsupportedAddressFamily
                         ),
-                    std::net::IpAddr::V6(addr) => self.v6.store
-                        .non_recursive_retrieve_prefix(
-                            PrefixId::<IPv6>::new(
-                                addr.into(),
-                                search_pfx.len(),
-                            ),
-                            // guard
-                        ).0.map_or(
-                            Err(PrefixStoreError::StoreNotReadyError),
-                            |p| Ok(p.is_ps_outdated(guard))
-                        )
+                    )*
                 }
             }
 
+            /// Recalculates the best and backup path for all the prefixes
+            /// in the store that have a record for `multi_uniq_id`, in both
+            /// address families and including the default routes, e.g.
+            /// after the session for it flapped.
+            ///
+            /// All these prefixes are recalculated, whether their path
+            /// selections are outdated or not, since marking a
+            /// `multi_uniq_id` as globally withdrawn or active does not
+            /// mark them as outdated. The withdrawn records, and the
+            /// records for globally withdrawn `multi_uniq_id`s, are left
+            /// out of the best path selection. This walks all the prefixes
+            /// in the store.
+            ///
+            /// Returns a report with the number of prefixes that were
+            /// checked and recalculated, and the number of best and backup
+            /// paths that changed. An error indicates an inconsistency in
+            /// the store, the prefixes before the failing one have been
+            /// recalculated.
+            pub fn recalculate_best_paths_for_mui(
+                &self,
+                mui: u32,
+                tbi: &<M as #krate::prelude::Meta>::TBI,
+                guard: &#krate::prelude::multi::Guard
+            ) -> Result<
+                #krate::prelude::multi::RecalculationReport,
+                #krate::prelude::multi::PrefixStoreError
+            > {
+                let mut report = #krate::prelude::multi::RecalculationReport::default();
+                #(
+                    let bmin = unsafe {
+                        self.#fields.store.withdrawn_muis_bmin.load(
+                            ::std::sync::atomic::Ordering::Acquire, guard
+                        ).deref()
+                    };
+                    for p_rec in self.#fields.store.stored_prefixes_iter(guard) {
+                        if p_rec.record_map.iter_records(Some(mui), guard).next().is_some() {
+                            report.prefixes_checked += 1;
+                            #recalculate
+                        }
+                    }
+                )*
+                Ok(report)
+            }
+
+            /// Recalculates the best and backup path for all the prefixes
+            /// in the store whose path selections are outdated, in both
+            /// address families and including the default routes, e.g.
+            /// after a change of policy.
+            ///
+            /// Like for `recalculate_best_paths_for_mui`, the withdrawn
+            /// records, and the records for globally withdrawn
+            /// `multi_uniq_id`s, are left out of the best path selection.
+            /// This walks all the prefixes in the store, without copying
+            /// their records.
+            ///
+            /// Returns a report with the number of prefixes that were
+            /// checked and recalculated, and the number of best and backup
+            /// paths that changed. An error indicates an inconsistency in
+            /// the store, the prefixes before the failing one have been
+            /// recalculated.
+            pub fn recalculate_all_outdated(
+                &self,
+                tbi: &<M as #krate::prelude::Meta>::TBI,
+                guard: &#krate::prelude::multi::Guard
+            ) -> Result<
+                #krate::prelude::multi::RecalculationReport,
+                #krate::prelude::multi::PrefixStoreError
+            > {
+                let mut report = #krate::prelude::multi::RecalculationReport::default();
+                #(
+                    let bmin = unsafe {
+                        self.#fields.store.withdrawn_muis_bmin.load(
+                            ::std::sync::atomic::Ordering::Acquire, guard
+                        ).deref()
+                    };
+                    for p_rec in self.#fields.store.stored_prefixes_iter(guard) {
+                        report.prefixes_checked += 1;
+                        if p_rec.is_ps_outdated(guard) {
+                            #recalculate
+                        }
+                    }
+                )*
+                Ok(report)
+            }
+
             /// Return a [QueryResult] that contains all the more-specific
             /// prefixes of the `search_pfx` in the store, including the
             /// meta-data of these prefixes.
@@ -770,31 +1783,33 @@ pub fn create_store(attr: TokenStream, item: TokenStream) -> TokenStream {
             /// QuerySet to contain references to the meta-data objects,
             /// instead of cloning them into it.
             pub fn more_specifics_from(&'a self,
-                search_pfx: &Prefix,
+                search_pfx: &#krate::prelude::Prefix,
                 mui: Option<u32>,
                 include_withdrawn: bool,
-                guard: &'a Guard,
-            ) -> QueryResult<M> {
+                guard: &'a #krate::prelude::multi::Guard,
+            ) -> #krate::prelude::QueryResult<M> {
 
                 match search_pfx.addr() {
-                    std::net::IpAddr::V4(addr) => self.v4.more_specifics_from(
-                        PrefixId::<IPv4>::new(
-                            addr.into(),
-                            search_pfx.len(),
-                        ),
-                        mui,
-                        include_withdrawn,
-                        guard
-                    ),
-                    std::net::IpAddr::V6(addr) => self.v6.more_specifics_from(
-                        PrefixId::<IPv6>::new(
-                            addr.into(),
-                            search_pfx.len(),
+                    #(
+                        ::std::net::IpAddr::#variants(addr) => self.#fields.more_specifics_from(
+                            #krate::prelude::multi::PrefixId::<#af_types>::new(
+                                addr.into(),
+                                search_pfx.len(),
+                            ),
+                            mui,
+                            include_withdrawn,
+                            guard
                         ),
-                        mui,
-                        include_withdrawn,
-                        guard
-                    ),
+                    )*
+                    #(
+                        ::std::net::IpAddr::#absent_variants(_) => #krate::prelude::QueryResult {
+                            match_type: #krate::prelude::MatchType::EmptyMatch,
+                            prefix: None,
+                            prefix_meta: vec![],
+                            less_specifics: None,
+                            more_specifics: None,
+                        },
+                    )*
                 }
             }
 
@@ -813,31 +1828,33 @@ pub fn create_store(attr: TokenStream, item: TokenStream) -> TokenStream {
             /// QuerySet to contain references to the meta-data objects,
             /// instead of cloning them into it.
             pub fn less_specifics_from(&'a self,
-                search_pfx: &Prefix,
+                search_pfx: &#krate::prelude::Prefix,
                 mui: Option<u32>,
                 include_withdrawn: bool,
-                guard: &'a Guard,
-            ) -> QueryResult<M> {
+                guard: &'a #krate::prelude::multi::Guard,
+            ) -> #krate::prelude::QueryResult<M> {
 
                 match search_pfx.addr() {
-                    std::net::IpAddr::V4(addr) => self.v4.less_specifics_from(
-                        PrefixId::<IPv4>::new(
-                            addr.into(),
-                            search_pfx.len(),
-                        ),
-                        mui,
-                        include_withdrawn,
-                        guard
-                    ),
-                    std::net::IpAddr::V6(addr) => self.v6.less_specifics_from(
-                        PrefixId::<IPv6>::new(
-                            addr.into(),
-                            search_pfx.len(),
+                    #(
+                        ::std::net::IpAddr::#variants(addr) => self.#fields.less_specifics_from(
+                            #krate::prelude::multi::PrefixId::<#af_types>::new(
+                                addr.into(),
+                                search_pfx.len(),
+                            ),
+                            mui,
+                            include_withdrawn,
+                            guard
                         ),
-                        mui,
-                        include_withdrawn,
-                        guard
-                    ),
+                    )*
+                    #(
+                        ::std::net::IpAddr::#absent_variants(_) => #krate::prelude::QueryResult {
+                            match_type: #krate::prelude::MatchType::EmptyMatch,
+                            prefix: None,
+                            prefix_meta: vec![],
+                            less_specifics: None,
+                            more_specifics: None,
+                        },
+                    )*
                 }
             }
 
@@ -888,46 +1905,32 @@ pub fn create_store(attr: TokenStream, item: TokenStream) -> TokenStream {
             /// }
             /// ```
             pub fn less_specifics_iter_from(&'a self,
-                search_pfx: &Prefix,
+                search_pfx: &#krate::prelude::Prefix,
                 mui: Option<u32>,
                 include_withdrawn: bool,
-                guard: &'a Guard,
-                ) -> impl Iterator<Item=PrefixRecord<M>> + 'a {
-                    let (left, right) = match search_pfx.addr() {
-                        std::net::IpAddr::V4(addr) => {
-                            (
-                                Some(self.v4.store.less_specific_prefix_iter(
-                                        PrefixId::<IPv4>::new(
-                                            addr.into(),
-                                            search_pfx.len(),
-                                        ),
-                                        mui,
-                                        include_withdrawn,
-                                        guard
-                                    )
-                                    .map(|p| PrefixRecord::from(p))
-                                ),
-                                None
-                            )
-                        }
-                        std::net::IpAddr::V6(addr) => {
-                            (
-                                None,
-                                Some(self.v6.store.less_specific_prefix_iter(
-                                        PrefixId::<IPv6>::new(
-                                            addr.into(),
-                                            search_pfx.len(),
-                                        ),
-                                        mui,
-                                        include_withdrawn,
-                                        guard
-                                    )
-                                    .map(|p| PrefixRecord::from(p))
+                guard: &'a #krate::prelude::multi::Guard,
+                ) -> impl Iterator<Item=#krate::prelude::PrefixRecord<M>> + 'a {
+                    // Only the tree for the address family of the
+                    // `search_pfx` yields prefixes.
+                    #(
+                        let #fields = match search_pfx.addr() {
+                            ::std::net::IpAddr::#variants(addr) => Some(
+                                self.#fields.store.less_specific_prefix_iter(
+                                    #krate::prelude::multi::PrefixId::<#af_types>::new(
+                                        addr.into(),
+                                        search_pfx.len(),
+                                    ),
+                                    mui,
+                                    include_withdrawn,
+                                    guard
                                 )
-                            )
-                        }
-                    };
-                    left.into_iter().flatten().chain(right.into_iter().flatten())
+                                .map(|p| #krate::prelude::PrefixRecord::from(p))
+                            ),
+                            _ => None,
+                        };
+                    )*
+                    ::std::iter::empty()
+                        #( .chain(#fields.into_iter().flatten()) )*
                 }
 
             /// Returns an iterator over all the more-specifics prefixes
@@ -976,123 +1979,265 @@ pub fn create_store(attr: TokenStream, item: TokenStream) -> TokenStream {
             /// }
             /// ```
             pub fn more_specifics_iter_from(&'a self,
-                search_pfx: &Prefix,
+                search_pfx: &#krate::prelude::Prefix,
                 mui: Option<u32>,
                 include_withdrawn: bool,
-                guard: &'a Guard,
-            ) -> impl Iterator<Item=PrefixRecord<M>> + 'a {
-
-                let (left, right) = match search_pfx.addr() {
-                    std::net::IpAddr::V4(addr) => {
-                        let bmin = unsafe {
-                            self.v4.store.withdrawn_muis_bmin.load(
-                                Ordering::Acquire, guard
-                            ).deref()
-                        };
-                        if mui.is_some() && bmin.contains(mui.unwrap()) {
-                                (None, None)
+                guard: &'a #krate::prelude::multi::Guard,
+            ) -> impl Iterator<Item=#krate::prelude::PrefixRecord<M>> + 'a {
+
+                // Only the tree for the address family of the
+                // `search_pfx` yields prefixes.
+                #(
+                    let #fields = match search_pfx.addr() {
+                        ::std::net::IpAddr::#variants(addr) => {
+                            let bmin = unsafe {
+                                self.#fields.store.withdrawn_muis_bmin.load(
+                                    ::std::sync::atomic::Ordering::Acquire, guard
+                                ).deref()
+                            };
+                            if mui.is_some() && bmin.contains(mui.unwrap()) {
+                                None
                             } else {
-                                (
-                                    Some(self.v4.store.more_specific_prefix_iter_from(
-                                            PrefixId::<IPv4>::new(
-                                                addr.into(),
-                                                search_pfx.len(),
-                                            ),
-                                            mui,
-                                            include_withdrawn,
-                                            guard
-                                        ).map(|p| PrefixRecord::from(p))
-                                    ),
-                                    None
-                                )
-                            }
-                        }
-                    std::net::IpAddr::V6(addr) => {
-                        let bmin = unsafe {
-                            self.v6.store.withdrawn_muis_bmin.load(
-                                Ordering::Acquire, guard
-                            ).deref()
-                        };
-                        if mui.is_some() && bmin.contains(mui.unwrap()) {
-                            (None, None)
-                        } else {
-                            (
-                                None,
-                                Some(self.v6.store.more_specific_prefix_iter_from(
-                                        PrefixId::<IPv6>::new(
+                                Some(self.#fields.store.more_specific_prefix_iter_from(
+                                        #krate::prelude::multi::PrefixId::<#af_types>::new(
                                             addr.into(),
                                             search_pfx.len(),
                                         ),
                                         mui,
                                         include_withdrawn,
                                         guard
-                                    ).map(|p| PrefixRecord::from(p))
+                                    ).map(|p| #krate::prelude::PrefixRecord::from(p))
                                 )
-                            )
+                            }
                         }
-                    }
-                };
-                left.into_iter().flatten().chain(right.into_iter().flatten())
+                        _ => None,
+                    };
+                )*
+                ::std::iter::empty()
+                    #( .chain(#fields.into_iter().flatten()) )*
             }
 
-            pub fn iter_records_for_mui_v4(
+            /// Returns an iterator over all the IPv4 and IPv6 prefixes in
+            /// the store that have a record for the `multi_uniq_id`, with
+            /// only that record in the meta-data. It first iterates over the
+            /// IPv4 prefixes and then over the IPv6 prefixes.
+            ///
+            /// If the `multi_uniq_id` is globally withdrawn for an address
+            /// family, the prefixes for that address family are skipped,
+            /// unless `include_withdrawn` is set, in which case they are
+            /// included with the records marked as `Withdrawn`. The other
+            /// address family is not affected.
+            ///
+            /// # Example
+            /// ```
+            /// use std::net::{Ipv4Addr, Ipv6Addr};
+            ///
+            /// use rotonda_store::prelude::*;
+            /// use rotonda_store::prelude::multi::*;
+            /// use rotonda_store::meta_examples::PrefixAs;
+            ///
+            /// let store = MultiThreadedStore::<PrefixAs>::new().unwrap();
+            /// let guard = &epoch::pin();
+            ///
+            /// let pfx4 = Prefix::new(
+            ///     "185.49.140.0".parse::<Ipv4Addr>().unwrap().into(), 22
+            /// ).unwrap();
+            /// let pfx6 = Prefix::new(
+            ///     "2a04:b900::".parse::<Ipv6Addr>().unwrap().into(), 29
+            /// ).unwrap();
+            /// for pfx in [pfx4, pfx6] {
+            ///     store.insert(
+            ///         &pfx,
+            ///         Record::new(1, 0, RouteStatus::Active, PrefixAs(211321)),
+            ///         None
+            ///     ).unwrap();
+            /// }
+            ///
+            /// let prefixes = |include_withdrawn| store
+            ///     .iter_records_for_mui(1, include_withdrawn, guard)
+            ///     .map(|p| p.prefix)
+            ///     .collect::<Vec<_>>();
+            ///
+            /// assert_eq!(prefixes(false), vec![pfx4, pfx6]);
+            ///
+            /// // Withdrawing the mui for IPv4 only skips the IPv4 prefixes.
+            /// store.mark_mui_as_withdrawn_v4(1).unwrap();
+            /// assert_eq!(prefixes(false), vec![pfx6]);
+            /// assert_eq!(prefixes(true), vec![pfx4, pfx6]);
+            ///
+            /// // Withdrawing the mui for IPv6 only skips the IPv6 prefixes.
+            /// store.mark_mui_as_active_v4(1).unwrap();
+            /// store.mark_mui_as_withdrawn_v6(1).unwrap();
+            /// assert_eq!(prefixes(false), vec![pfx4]);
+            /// assert_eq!(prefixes(true), vec![pfx4, pfx6]);
+            /// ```
+            pub fn iter_records_for_mui(
                 &'a self,
                 mui: u32,
                 include_withdrawn: bool,
-                guard: &'a Guard
-            ) -> impl Iterator<Item=PrefixRecord<M>> +'a {
+                guard: &'a #krate::prelude::multi::Guard
+            ) -> impl Iterator<Item=#krate::prelude::PrefixRecord<M>> +'a {
+                self.iter_records_for_mui_within(mui, None, include_withdrawn, guard)
+            }
 
-                let bmin = unsafe {
-                    self.v4.store.withdrawn_muis_bmin.load(
-                        Ordering::Acquire, guard
-                    ).deref()
+            /// Returns an iterator over the prefixes in the store that have
+            /// a record for the `multi_uniq_id`, like `iter_records_for_mui`,
+            /// but only the ones within the covering prefix `within`, if it
+            /// is set.
+            ///
+            /// The covering prefix itself is included if it has a record for
+            /// the `multi_uniq_id`. Only the tree for the address family of
+            /// the covering prefix is walked, and only from the covering
+            /// prefix down. If `within` is `None` the prefixes of both
+            /// address families are returned.
+            ///
+            /// # Example
+            /// ```
+            /// use std::net::Ipv4Addr;
+            ///
+            /// use rotonda_store::prelude::*;
+            /// use rotonda_store::prelude::multi::*;
+            /// use rotonda_store::meta_examples::PrefixAs;
+            ///
+            /// let store = MultiThreadedStore::<PrefixAs>::new().unwrap();
+            /// let guard = &epoch::pin();
+            ///
+            /// let pfx = |addr: &str, len| Prefix::new(
+            ///     addr.parse::<Ipv4Addr>().unwrap().into(), len
+            /// ).unwrap();
+            /// for p in [pfx("10.0.0.0", 8), pfx("10.1.0.0", 16), pfx("192.0.2.0", 24)] {
+            ///     store.insert(
+            ///         &p,
+            ///         Record::new(1, 0, RouteStatus::Active, PrefixAs(211321)),
+            ///         None
+            ///     ).unwrap();
+            /// }
+            ///
+            /// let within = pfx("10.0.0.0", 8);
+            /// let mut prefixes = store
+            ///     .iter_records_for_mui_within(1, Some(&within), false, guard)
+            ///     .map(|p| p.prefix)
+            ///     .collect::<Vec<_>>();
+            /// prefixes.sort_by_key(|p| (p.addr(), p.len()));
+            ///
+            /// assert_eq!(prefixes, vec![pfx("10.0.0.0", 8), pfx("10.1.0.0", 16)]);
+            /// ```
+            pub fn iter_records_for_mui_within(
+                &'a self,
+                mui: u32,
+                within: Option<&#krate::prelude::Prefix>,
+                include_withdrawn: bool,
+                guard: &'a #krate::prelude::multi::Guard
+            ) -> impl Iterator<Item=#krate::prelude::PrefixRecord<M>> +'a {
+                let within = within.copied();
+                let (v4, v6) = match within.map(|p| p.addr()) {
+                    Some(::std::net::IpAddr::V4(_)) => (true, false),
+                    Some(::std::net::IpAddr::V6(_)) => (false, true),
+                    None => (true, true),
                 };
 
-                if bmin.contains(mui) && !include_withdrawn {
-                    None
-                } else {
-                    Some(
-                        self.v4.store.more_specific_prefix_iter_from(
-                                PrefixId::<IPv4>::new(
-                                    0,
-                                    0,
-                                ),
-                                Some(mui),
-                                include_withdrawn,
-                                guard
-                            ).map(|p| PrefixRecord::from(p))
+                v4.then(|| self.records_for_mui_v4(mui, within, include_withdrawn, guard))
+                    .into_iter()
+                    .flatten()
+                    .chain(
+                        v6.then(|| self.records_for_mui_v6(mui, within, include_withdrawn, guard))
+                            .into_iter()
+                            .flatten()
                     )
-                }.into_iter().flatten()
             }
 
+            /// Returns an iterator over the prefixes in the store with the
+            /// records that changed after the logical time `ltime`, i.e.
+            /// that were inserted or replaced with a logical time greater
+            /// than `ltime`, or whose status changed after it.
+            ///
+            /// Only the changed records are returned for each prefix,
+            /// including withdrawn records, so that a client can poll for
+            /// incremental updates by keeping the highest logical time it
+            /// has seen. The records have their status as seen by the
+            /// store, i.e. `Withdrawn` if their `multi_uniq_id` is globally
+            /// withdrawn for the address family, and their local status
+            /// otherwise, and the logical time of their last change.
+            ///
+            /// A change of the local status with
+            /// `mark_mui_as_withdrawn_for_prefix` or
+            /// `mark_mui_as_active_for_prefix`, or of the global status
+            /// with `mark_mui_as_withdrawn`, `mark_mui_as_active` or their
+            /// per address family variants, does not touch the logical time
+            /// of the records. It gets a logical time one above the highest
+            /// logical time of the records inserted so far, and of the
+            /// status changes before it, so a client only sees the records
+            /// that are inserted after a status change if these have a
+            /// logical time above it. A change of the global status
+            /// returns all the records for the `multi_uniq_id` in the
+            /// address family, and every successful call is a change, also
+            /// if the status already was the same.
+            ///
+            /// Each call walks all the prefixes in the store, and copies the
+            /// status changes after `ltime`, but only clones the changed
+            /// records. The store keeps the logical time of the last change
+            /// of the local status of every `(prefix, multi_uniq_id)` and of
+            /// the global status of every `multi_uniq_id`.
+            ///
+            /// All IPv4 prefixes are returned before all IPv6 prefixes, and
+            /// within an address family the prefixes are returned in no
+            /// particular order.
+            pub fn changes_since(
+                &'a self,
+                ltime: u64,
+                guard: &'a #krate::prelude::multi::Guard
+            ) -> impl Iterator<Item=#krate::prelude::PrefixRecord<M>> + 'a {
+                let v4 = { #changes_since_v4 };
+                let v6 = { #changes_since_v6 };
+                v4.chain(v6)
+            }
+
+            /// Returns an iterator over all the IPv4 prefixes in the store
+            /// that have a record for the `multi_uniq_id`. See
+            /// `iter_records_for_mui`.
+            pub fn iter_records_for_mui_v4(
+                &'a self,
+                mui: u32,
+                include_withdrawn: bool,
+                guard: &'a #krate::prelude::multi::Guard
+            ) -> impl Iterator<Item=#krate::prelude::PrefixRecord<M>> +'a {
+                self.records_for_mui_v4(mui, None, include_withdrawn, guard)
+            }
+
+            /// Returns an iterator over all the IPv6 prefixes in the store
+            /// that have a record for the `multi_uniq_id`. See
+            /// `iter_records_for_mui`.
             pub fn iter_records_for_mui_v6(
                 &'a self,
                 mui: u32,
                 include_withdrawn: bool,
-                guard: &'a Guard
-            ) -> impl Iterator<Item=PrefixRecord<M>> +'a {
+                guard: &'a #krate::prelude::multi::Guard
+            ) -> impl Iterator<Item=#krate::prelude::PrefixRecord<M>> +'a {
+                self.records_for_mui_v6(mui, None, include_withdrawn, guard)
+            }
 
-                let bmin = unsafe {
-                    self.v4.store.withdrawn_muis_bmin.load(
-                        Ordering::Acquire, guard
-                    ).deref()
-                };
+            // The IPv4 prefixes with a record for the `multi_uniq_id`,
+            // within the IPv4 prefix `within`, if any.
+            fn records_for_mui_v4(
+                &'a self,
+                mui: u32,
+                within: Option<#krate::prelude::Prefix>,
+                include_withdrawn: bool,
+                guard: &'a #krate::prelude::multi::Guard
+            ) -> impl Iterator<Item=#krate::prelude::PrefixRecord<M>> +'a {
+                #iter_records_for_mui_v4
+            }
 
-                if bmin.contains(mui) && !include_withdrawn {
-                    None
-                } else {
-                    Some(
-                        self.v6.store.more_specific_prefix_iter_from(
-                                PrefixId::<IPv6>::new(
-                                    0,
-                                    0,
-                                ),
-                                Some(mui),
-                                include_withdrawn,
-                                guard
-                            ).map(|p| PrefixRecord::from(p))
-                    )
-                }.into_iter().flatten()
+            // The IPv6 prefixes with a record for the `multi_uniq_id`,
+            // within the IPv6 prefix `within`, if any.
+            fn records_for_mui_v6(
+                &'a self,
+                mui: u32,
+                within: Option<#krate::prelude::Prefix>,
+                include_withdrawn: bool,
+                guard: &'a #krate::prelude::multi::Guard
+            ) -> impl Iterator<Item=#krate::prelude::PrefixRecord<M>> +'a {
+                #iter_records_for_mui_v6
             }
 
             /// Insert or replace a Record into the Store
@@ -1109,26 +2254,148 @@ pub fn create_store(attr: TokenStream, item: TokenStream) -> TokenStream {
             /// Returns some metrics about the resulting insert.
             pub fn insert(
                 &self,
-                prefix: &Prefix,
-                record: Record<M>,
+                prefix: &#krate::prelude::Prefix,
+                record: #krate::prelude::Record<M>,
                 update_path_selections: Option<M::TBI>
-            ) -> Result<UpsertReport, PrefixStoreError> {
+            ) -> Result<#krate::prelude::multi::UpsertReport, #krate::prelude::multi::PrefixStoreError> {
                 match prefix.addr() {
-                    std::net::IpAddr::V4(addr) => {
-                        self.v4.insert(
-                            PrefixId::<IPv4>::from(*prefix),
-                            record,
-                            update_path_selections,
-                        )
+                    #(
+                        ::std::net::IpAddr::#variants(_) => {
+                            let id = #krate::prelude::multi::PrefixId::<#af_types>::from(*prefix);
+                            let guard = &#krate::prelude::multi::epoch::pin();
+                            let path_selections = || self.#fields.store
+                                .non_recursive_retrieve_prefix(id)
+                                .0
+                                .map_or((None, None), |p_rec| {
+                                    let ps = unsafe { p_rec.get_path_selections(guard) };
+                                    (ps.best(), ps.backup())
+                                });
+
+                            let old = (
+                                update_path_selections.is_some()
+                                    && self.best_path_observer.get().is_some()
+                            ).then(path_selections);
+                            self.status_changes.seen(record.ltime);
+                            let report = self.#fields.insert(id, record, update_path_selections)?;
+                            if let Some(old) = old {
+                                self.notify_best_path_change(*prefix, old, path_selections());
+                            }
+                            Ok(report)
+                        }
+                    )*
+                    #(
+                        ::std::net::IpAddr::#absent_variants(_) => Err(
+                            #krate::prelude::multi::PrefixStoreError::UnsupportedAddressFamily
+                        ),
+                    )*
+                }
+            }
+
+            /// Insert or replace a batch of Records into the Store.
+            ///
+            /// Each `(prefix, record)` pair is inserted like with `insert`,
+            /// but the prefixes are first partitioned per address family,
+            /// so that a prefix for an address family the store does not
+            /// have a tree for fails the whole batch before anything is
+            /// inserted. Records that share their meta-data for a number of
+            /// prefixes, e.g. the NLRI in a BGP UPDATE message, can be
+            /// inserted by cloning the record for each prefix.
+            ///
+            /// If the `update_path_selections` argument is used the best path
+            /// selection is run once for each prefix in the batch, after all
+            /// the records have been inserted.
+            ///
+            /// Returns a report with the number of new prefixes, new and
+            /// replaced records, and the contention retries for the whole
+            /// batch. If an insert fails, the error is returned right away,
+            /// and the records that were inserted before stay in the store.
+            ///
+            /// # Example
+            /// ```
+            /// use std::net::Ipv4Addr;
+            ///
+            /// use rotonda_store::prelude::*;
+            /// use rotonda_store::prelude::multi::*;
+            /// use rotonda_store::meta_examples::PrefixAs;
+            ///
+            /// let store = MultiThreadedStore::<PrefixAs>::new().unwrap();
+            ///
+            /// let pfx_addr = "185.49.140.0".parse::<Ipv4Addr>()
+            ///         .unwrap()
+            ///         .into();
+            /// let our_asn = Record::new(0, 0, RouteStatus::Active, PrefixAs(211321));
+            ///
+            /// let report = store.insert_batch(
+            ///     (22..=25).map(|len| {
+            ///         (Prefix::new(pfx_addr, len).unwrap(), our_asn.clone())
+            ///     }),
+            ///     None
+            /// ).unwrap();
+            ///
+            /// assert_eq!(report.prefixes_new, 4);
+            /// assert_eq!(report.records_new, 4);
+            /// assert_eq!(report.records_replaced, 0);
+            /// ```
+            pub fn insert_batch(
+                &self,
+                records: impl IntoIterator<Item=(#krate::prelude::Prefix, #krate::prelude::Record<M>)>,
+                update_path_selections: Option<M::TBI>
+            ) -> Result<#krate::prelude::multi::BatchReport, #krate::prelude::multi::PrefixStoreError> {
+                #( let mut #fields = vec![]; )*
+                for (prefix, record) in records {
+                    match prefix.addr() {
+                        #(
+                            ::std::net::IpAddr::#variants(_) => {
+                                #fields.push((prefix, record))
+                            }
+                        )*
+                        #(
+                            ::std::net::IpAddr::#absent_variants(_) => {
+                                return Err(
+                                    #krate::prelude::multi::PrefixStoreError::UnsupportedAddressFamily
+                                );
+                            }
+                        )*
                     }
-                    std::net::IpAddr::V6(addr) => {
-                        self.v6.insert(
-                            PrefixId::<IPv6>::from(*prefix),
+                }
+
+                let mut report = #krate::prelude::multi::BatchReport::default();
+                let mut prefixes = vec![];
+                #(
+                    for (prefix, record) in #fields {
+                        self.status_changes.seen(record.ltime);
+                        let upsert = self.#fields.insert(
+                            #krate::prelude::multi::PrefixId::<#af_types>::from(prefix),
                             record,
-                            update_path_selections,
-                        )
+                            None,
+                        )?;
+                        if upsert.prefix_new {
+                            report.prefixes_new += 1;
+                        }
+                        if upsert.mui_new {
+                            report.records_new += 1;
+                        } else {
+                            report.records_replaced += 1;
+                        }
+                        report.cas_count += upsert.cas_count;
+                        prefixes.push(prefix);
+                    }
+                )*
+
+                if let Some(tbi) = update_path_selections {
+                    let guard = &#krate::prelude::multi::epoch::pin();
+                    prefixes.sort();
+                    prefixes.dedup();
+                    for prefix in prefixes {
+                        self.calculate_and_store_best_and_backup_path(
+                            &prefix,
+                            &tbi,
+                            guard
+                        )?;
                     }
                 }
+
+                Ok(report)
             }
 
             /// Returns an unordered iterator over all prefixes, with any
@@ -1177,13 +2444,14 @@ pub fn create_store(attr: TokenStream, item: TokenStream) -> TokenStream {
             /// ```
             pub fn prefixes_iter(
                 &'a self,
-            ) -> impl Iterator<Item=PrefixRecord<M>> + 'a {
-                self.v4.store.prefixes_iter()
-                    .map(|p| PrefixRecord::from(p))
-                    .chain(
-                        self.v6.store.prefixes_iter()
-                        .map(|p| PrefixRecord::from(p))
-                    )
+            ) -> impl Iterator<Item=#krate::prelude::PrefixRecord<M>> + 'a {
+                ::std::iter::empty()
+                    #(
+                        .chain(
+                            self.#fields.store.prefixes_iter()
+                            .map(|p| #krate::prelude::PrefixRecord::from(p))
+                        )
+                    )*
             }
 
             /// Returns an unordered iterator over all IPv4 prefixes in the
@@ -1228,9 +2496,8 @@ pub fn create_store(attr: TokenStream, item: TokenStream) -> TokenStream {
             /// ```
             pub fn prefixes_iter_v4(
                 &'a self,
-            ) -> impl Iterator<Item=PrefixRecord<M>> + 'a {
-                self.v4.store.prefixes_iter()
-                    .map(|p| PrefixRecord::from(p))
+            ) -> impl Iterator<Item=#krate::prelude::PrefixRecord<M>> + 'a {
+                #prefixes_iter_v4
             }
 
             /// Returns an unordered iterator over all IPv6 prefixes in the
@@ -1275,9 +2542,99 @@ pub fn create_store(attr: TokenStream, item: TokenStream) -> TokenStream {
             /// ```
             pub fn prefixes_iter_v6(
                 &'a self,
-            ) -> impl Iterator<Item=PrefixRecord<M>> + 'a {
-                self.v6.store.prefixes_iter()
-                    .map(|p| PrefixRecord::from(p))
+            ) -> impl Iterator<Item=#krate::prelude::PrefixRecord<M>> + 'a {
+                #prefixes_iter_v6
+            }
+
+            /// Returns an iterator over all the prefixes in the store, with
+            /// their records, in address-then-length order, first for IPv4
+            /// and then for IPv6.
+            ///
+            /// Unlike `prefixes_iter`, this walks the tree in order, and
+            /// takes the prefixes from a part of the tree at a time, so the
+            /// iterator does not hold on to the whole store. Prefixes that
+            /// are inserted in the meantime are returned if their part of
+            /// the tree has not been taken yet. Like with `prefixes_iter`,
+            /// records with any status are returned, including withdrawn
+            /// ones.
+            pub fn prefixes_iter_ordered(
+                &'a self,
+            ) -> impl Iterator<Item=#krate::prelude::PrefixRecord<M>> + 'a {
+                self.prefixes_iter_v4_ordered()
+                    .chain(self.prefixes_iter_v6_ordered())
+            }
+
+            /// Returns an iterator over all the IPv4 prefixes in the store,
+            /// with their records, in address-then-length order.
+            ///
+            /// See `prefixes_iter_ordered` for how this differs from
+            /// `prefixes_iter_v4`.
+            pub fn prefixes_iter_v4_ordered(
+                &'a self,
+            ) -> impl Iterator<Item=#krate::prelude::PrefixRecord<M>> + 'a {
+                self.prefixes_ordered_v4(None)
+            }
+
+            /// Returns an iterator over all the IPv6 prefixes in the store,
+            /// with their records, in address-then-length order.
+            ///
+            /// See `prefixes_iter_ordered` for how this differs from
+            /// `prefixes_iter_v6`.
+            pub fn prefixes_iter_v6_ordered(
+                &'a self,
+            ) -> impl Iterator<Item=#krate::prelude::PrefixRecord<M>> + 'a {
+                self.prefixes_ordered_v6(None)
+            }
+
+            // The IPv4 prefixes in address-then-length order, from the IPv4
+            // prefix `from` on, if any.
+            fn prefixes_ordered_v4(
+                &'a self,
+                from: Option<#krate::prelude::Prefix>,
+            ) -> impl Iterator<Item=#krate::prelude::PrefixRecord<M>> + 'a {
+                #prefixes_ordered_v4
+            }
+
+            // The IPv6 prefixes in address-then-length order, from the IPv6
+            // prefix `from` on, if any.
+            fn prefixes_ordered_v6(
+                &'a self,
+                from: Option<#krate::prelude::Prefix>,
+            ) -> impl Iterator<Item=#krate::prelude::PrefixRecord<M>> + 'a {
+                #prefixes_ordered_v6
+            }
+
+            /// Returns an iterator over the prefixes in the store from
+            /// `start`, inclusive, up to `end`, exclusive, with their
+            /// records, in the order of `prefixes_iter_ordered`, i.e.
+            /// address-then-length, with all IPv4 prefixes before all IPv6
+            /// prefixes.
+            ///
+            /// `start` and `end` do not have to be in the store. To page
+            /// through the store, take a number of prefixes from the
+            /// iterator, and start the next page at the first prefix that
+            /// was not taken. The tree for an address family is only walked
+            /// if the range includes prefixes of that family, and the walk
+            /// starts at `start`, so a page costs about the same wherever it
+            /// is in the store.
+            pub fn prefixes_in_range(
+                &'a self,
+                start: &#krate::prelude::Prefix,
+                end: &#krate::prelude::Prefix,
+            ) -> impl Iterator<Item=#krate::prelude::PrefixRecord<M>> + 'a {
+                let key = |p: &#krate::prelude::Prefix| (p.addr(), p.len());
+                let (start, end_key) = (*start, key(end));
+
+                // All IPv4 addresses sort before all IPv6 addresses.
+                let v4 = start.addr().is_ipv4().then(|| self.prefixes_ordered_v4(Some(start)));
+                let v6 = end_key.0.is_ipv6().then(|| {
+                    self.prefixes_ordered_v6(start.addr().is_ipv6().then_some(start))
+                });
+
+                v4.into_iter()
+                    .flatten()
+                    .chain(v6.into_iter().flatten())
+                    .take_while(move |p| key(&p.prefix) < end_key)
             }
 
             /// Change the local status of the record for the combination of
@@ -1286,25 +2643,25 @@ pub fn create_store(attr: TokenStream, item: TokenStream) -> TokenStream {
             /// of a record.
             pub fn mark_mui_as_withdrawn_for_prefix(
                 &self,
-                prefix: &Prefix,
+                prefix: &#krate::prelude::Prefix,
                 mui: u32
-            ) -> Result<(), PrefixStoreError> {
-                let guard = &epoch::pin();
+            ) -> Result<(), #krate::prelude::multi::PrefixStoreError> {
+                let guard = &#krate::prelude::multi::epoch::pin();
                 match prefix.addr() {
-                    std::net::IpAddr::V4(addr) => {
-                        self.v4.store.mark_mui_as_withdrawn_for_prefix(
-                            PrefixId::<IPv4>::from(*prefix),
-                            mui,
-                            // &guard
-                        )
-                    }
-                    std::net::IpAddr::V6(addr) => {
-                        self.v6.store.mark_mui_as_withdrawn_for_prefix(
-                            PrefixId::<IPv6>::from(*prefix),
-                            mui,
-                            // &guard
-                        )
-                    }
+                    #(
+                        ::std::net::IpAddr::#variants(_) => {
+                            self.#fields.store.mark_mui_as_withdrawn_for_prefix(
+                                #krate::prelude::multi::PrefixId::<#af_types>::from(*prefix),
+                                mui,
+                                // &guard
+                            ).map(|()| self.status_changes.record_changed(*prefix, mui))
+                        }
+                    )*
+                    #(
+                        ::std::net::IpAddr::#absent_variants(_) => Err(
+                            #krate::prelude::multi::PrefixStoreError::UnsupportedAddressFamily
+                        ),
+                    )*
                 }
             }
 
@@ -1314,25 +2671,25 @@ pub fn create_store(attr: TokenStream, item: TokenStream) -> TokenStream {
             /// of a record.
             pub fn mark_mui_as_active_for_prefix(
                 &self,
-                prefix: &Prefix,
+                prefix: &#krate::prelude::Prefix,
                 mui: u32
-            ) -> Result<(), PrefixStoreError> {
-                let guard = &epoch::pin();
+            ) -> Result<(), #krate::prelude::multi::PrefixStoreError> {
+                let guard = &#krate::prelude::multi::epoch::pin();
                 match prefix.addr() {
-                    std::net::IpAddr::V4(addr) => {
-                        self.v4.store.mark_mui_as_active_for_prefix(
-                            PrefixId::<IPv4>::from(*prefix),
-                            mui,
-                            // &guard
-                        )
-                    }
-                    std::net::IpAddr::V6(addr) => {
-                        self.v6.store.mark_mui_as_active_for_prefix(
-                            PrefixId::<IPv6>::from(*prefix),
-                            mui,
-                            // &guard
-                        )
-                    }
+                    #(
+                        ::std::net::IpAddr::#variants(_) => {
+                            self.#fields.store.mark_mui_as_active_for_prefix(
+                                #krate::prelude::multi::PrefixId::<#af_types>::from(*prefix),
+                                mui,
+                                // &guard
+                            ).map(|()| self.status_changes.record_changed(*prefix, mui))
+                        }
+                    )*
+                    #(
+                        ::std::net::IpAddr::#absent_variants(_) => Err(
+                            #krate::prelude::multi::PrefixStoreError::UnsupportedAddressFamily
+                        ),
+                    )*
                 }
             }
 
@@ -1343,13 +2700,8 @@ pub fn create_store(attr: TokenStream, item: TokenStream) -> TokenStream {
             pub fn mark_mui_as_active_v4(
                 &self,
                 mui: u32
-            ) -> Result<(), PrefixStoreError> {
-                let guard = &epoch::pin();
-
-                self.v4.store.mark_mui_as_active(
-                    mui,
-                    &guard
-                )
+            ) -> Result<(), #krate::prelude::multi::PrefixStoreError> {
+                #mark_mui_as_active_v4
             }
 
             /// Change the status of all records for IPv4 prefixes for this
@@ -1361,13 +2713,8 @@ pub fn create_store(attr: TokenStream, item: TokenStream) -> TokenStream {
             pub fn mark_mui_as_withdrawn_v4(
                 &self,
                 mui: u32
-            ) -> Result<(), PrefixStoreError> {
-                let guard = &epoch::pin();
-
-                self.v4.store.mark_mui_as_withdrawn(
-                    mui,
-                    &guard
-                )
+            ) -> Result<(), #krate::prelude::multi::PrefixStoreError> {
+                #mark_mui_as_withdrawn_v4
             }
 
             /// Change the status of all records for IPv6 prefixes for this
@@ -1377,13 +2724,8 @@ pub fn create_store(attr: TokenStream, item: TokenStream) -> TokenStream {
             pub fn mark_mui_as_active_v6(
                 &self,
                 mui: u32
-            ) -> Result<(), PrefixStoreError> {
-                let guard = &epoch::pin();
-
-                self.v6.store.mark_mui_as_active(
-                    mui,
-                    &guard
-                )
+            ) -> Result<(), #krate::prelude::multi::PrefixStoreError> {
+                #mark_mui_as_active_v6
             }
 
             /// Change the status of all records for IPv6 prefixes for this
@@ -1395,13 +2737,8 @@ pub fn create_store(attr: TokenStream, item: TokenStream) -> TokenStream {
             pub fn mark_mui_as_withdrawn_v6(
                 &self,
                 mui: u32
-            ) -> Result<(), PrefixStoreError> {
-                let guard = &epoch::pin();
-
-                self.v6.store.mark_mui_as_withdrawn(
-                    mui,
-                    &guard
-                )
+            ) -> Result<(), #krate::prelude::multi::PrefixStoreError> {
+                #mark_mui_as_withdrawn_v6
             }
 
 
@@ -1415,22 +2752,56 @@ pub fn create_store(attr: TokenStream, item: TokenStream) -> TokenStream {
             pub fn mark_mui_as_withdrawn(
                 &self,
                 mui: u32
-            ) -> Result<(), PrefixStoreError> {
-                let guard = &epoch::pin();
+            ) -> Result<(), #krate::prelude::multi::PrefixStoreError> {
+                let guard = &#krate::prelude::multi::epoch::pin();
 
-                let res_v4 = self.v4.store.mark_mui_as_withdrawn(
-                    mui,
-                    &guard
-                );
-                let res_v6 = self.v6.store.mark_mui_as_withdrawn(
-                    mui,
-                    &guard
-                );
+                let res = Ok(());
+                #(
+                    let res = res.and(self.#fields.store.mark_mui_as_withdrawn(
+                        mui,
+                        &guard
+                    ).map(|()| self.status_changes.mui_changed(&self.status_changes.#fields, mui)));
+                )*
+
+                res
+            }
+
+            /// Change the status of all records for this `multi_uniq_id` to
+            /// Active.
+            ///
+            /// This method tries to mark all records: first the IPv4 records,
+            /// then the IPv6 records. If marking of the IPv4 records fails,
+            /// the method continues and tries to mark the IPv6 records. The
+            /// returned report has the result for each address family, or
+            /// `None` for an address family the store does not have a tree
+            /// for.
+            #[must_use = "the report has the result for each address family"]
+            pub fn mark_mui_as_active(
+                &self,
+                mui: u32
+            ) -> #krate::prelude::multi::MuiReport {
+                let guard = &#krate::prelude::multi::epoch::pin();
 
-                res_v4.and(res_v6)
+                #krate::prelude::multi::MuiReport {
+                    v4: #mark_mui_as_active_report_v4,
+                    v6: #mark_mui_as_active_report_v6,
+                }
             }
 
+            /// Returns the global status of the `multi_uniq_id` for IPv4 and
+            /// IPv6, either `Active` or `Withdrawn`. The status is `None`
+            /// for an address family the store does not have a tree for.
+            pub fn mui_status(
+                &self,
+                mui: u32
+            ) -> #krate::prelude::multi::MuiStatus {
+                let guard = &#krate::prelude::multi::epoch::pin();
 
+                #krate::prelude::multi::MuiStatus {
+                    v4: #mui_status_v4,
+                    v6: #mui_status_v6,
+                }
+            }
 
             // Whether the global status for IPv4 prefixes and the specified
             // `multi_uniq_id` is set to `Withdrawn`.
@@ -1438,9 +2809,7 @@ pub fn create_store(attr: TokenStream, item: TokenStream) -> TokenStream {
                 &self,
                 mui: u32
             ) -> bool {
-                let guard = &epoch::pin();
-
-                self.v4.store.mui_is_withdrawn(mui, guard)
+                #mui_is_withdrawn_v4
             }
 
             // Whether the global status for IPv6 prefixes and the specified
@@ -1449,9 +2818,7 @@ pub fn create_store(attr: TokenStream, item: TokenStream) -> TokenStream {
                 &self,
                 mui: u32
             ) -> bool {
-                let guard = &epoch::pin();
-
-                self.v6.store.mui_is_withdrawn(mui, guard)
+                #mui_is_withdrawn_v6
             }
 
             /// Returns the number of all prefixes in the store.
@@ -1459,8 +2826,7 @@ pub fn create_store(attr: TokenStream, item: TokenStream) -> TokenStream {
             /// Note that this method will actually traverse the complete
             /// tree.
             pub fn prefixes_count(&self) -> usize {
-                self.v4.store.get_prefixes_count()
-                + self.v6.store.get_prefixes_count()
+                0 #( + self.#fields.store.get_prefixes_count() )*
             }
 
             /// Returns the number of all IPv4 prefixes in the store.
@@ -1469,7 +2835,7 @@ pub fn create_store(attr: TokenStream, item: TokenStream) -> TokenStream {
             /// number in the store, due to contention at the time of
             /// reading the value.
             pub fn prefixes_v4_count(&self) -> usize {
-                self.v4.store.get_prefixes_count()
+                #prefixes_v4_count
             }
 
             /// Returns the number of all IPv4 prefixes with the
@@ -1479,7 +2845,7 @@ pub fn create_store(attr: TokenStream, item: TokenStream) -> TokenStream {
             /// number in the store, due to contention at the time of
             /// reading the value.
             pub fn prefixes_v4_count_for_len(&self, len: u8) -> usize {
-                self.v4.store.get_prefixes_count_for_len(len)
+                #prefixes_v4_count_for_len
             }
 
             /// Returns the number of all IPv6 prefixes in the store.
@@ -1488,7 +2854,7 @@ pub fn create_store(attr: TokenStream, item: TokenStream) -> TokenStream {
             /// number in the store, due to contention at the time of
             /// reading the value.
             pub fn prefixes_v6_count(&self) -> usize {
-                self.v6.store.get_prefixes_count()
+                #prefixes_v6_count
             }
 
             /// Returns the number of all IPv6 prefixes with the
@@ -1498,7 +2864,7 @@ pub fn create_store(attr: TokenStream, item: TokenStream) -> TokenStream {
             /// number in the store, due to contention at the time of
             /// reading the value.
             pub fn prefixes_v6_count_for_len(&self, len: u8) -> usize {
-                self.v6.store.get_prefixes_count_for_len(len)
+                #prefixes_v6_count_for_len
             }
 
             /// Returns the number of nodes in the store.
@@ -1507,8 +2873,7 @@ pub fn create_store(attr: TokenStream, item: TokenStream) -> TokenStream {
             /// number in the store, due to contention at the time of
             /// reading the value.
             pub fn nodes_count(&self) -> usize {
-                self.v4.store.get_nodes_count()
-                + self.v6.store.get_nodes_count()
+                0 #( + self.#fields.store.get_nodes_count() )*
             }
 
             /// Returns the number of IPv4 nodes in the store.
@@ -1517,7 +2882,7 @@ pub fn create_store(attr: TokenStream, item: TokenStream) -> TokenStream {
             /// number in the store, due to contention at the time of
             /// reading the value.
             pub fn nodes_v4_count(&self) -> usize {
-                self.v4.store.get_nodes_count()
+                #nodes_v4_count
             }
 
             /// Returns the number of IPv6 nodes in the store.
@@ -1526,33 +2891,115 @@ pub fn create_store(attr: TokenStream, item: TokenStream) -> TokenStream {
             /// number in the store, due to contention at the time of
             /// reading the value.
             pub fn nodes_v6_count(&self) -> usize {
-                self.v6.store.get_nodes_count()
+                #nodes_v6_count
             }
 
             /// Print the store statistics to the standard output.
             #[cfg(feature = "cli")]
             pub fn print_funky_stats(&self) {
                 println!("");
-                println!("Stats for IPv4 multi-threaded store\n");
-                println!("{}", self.v4);
-                println!("Stats for IPv6 multi-threaded store\n");
-                println!("{}", self.v6);
+                #(
+                    println!("Stats for {} multi-threaded store\n", stringify!(#ip_afs));
+                    println!("{}", self.#fields);
+                )*
             }
 
             // The Store statistics.
-            pub fn stats(&self) -> StoreStats {
-                StoreStats {
-                    v4: self.v4.store.counters.get_prefix_stats(),
-                    v6: self.v6.store.counters.get_prefix_stats(),
+            pub fn stats(&self) -> #krate::prelude::multi::StoreStats {
+                #krate::prelude::multi::StoreStats {
+                    v4: #stats_v4,
+                    v6: #stats_v6,
                 }
             }
         }
     };
 
+    #[cfg(feature = "snapshot")]
+    let snapshot =
+        crate::snapshot::snapshot_impl(krate, &cfgs, store_name, &fields, &ip_afs, &af_types);
+    #[cfg(not(feature = "snapshot"))]
+    let snapshot = quote! {};
+
+    #[cfg(feature = "mrt")]
+    let mrt = crate::mrt::mrt_impl(krate, &cfgs, store_name);
+    #[cfg(not(feature = "mrt"))]
+    let mrt = quote! {};
+
     let result = quote! {
         #create_strides
         #store
+        #mrt
+        #snapshot
     };
 
-    TokenStream::from(result)
+    result
+}
+
+// ---------- Meta derive ----------------------------------------------------
+
+/// Derives the `Meta` trait of rotonda-store for a struct, with a best path
+/// selection that follows the BGP decision process.
+///
+/// The fields that take part in the best path selection are marked with
+/// `#[meta(local_pref)]`, `#[meta(as_path_len)]`, `#[meta(origin)]`,
+/// `#[meta(neighbor_as)]`, `#[meta(med)]`, `#[meta(bgp_id)]` and
+/// `#[meta(peer_addr)]`. A path is preferred over another one if it has a
+/// higher local preference, then if it has a shorter AS path, then if it has
+/// a lower origin, then if it has a lower MED, then if it has a lower BGP
+/// identifier, and then if it has a lower peer address, whatever the order
+/// of the fields in the struct. Each marker can be used on one field at
+/// most, and markers that are not used are skipped. The types of the marked
+/// fields need to implement `Ord`.
+///
+/// The MED is only compared between paths from the same neighbor AS if
+/// there is a field marked `neighbor_as`, otherwise it is compared between
+/// all paths. Since the best path selection needs a total order, paths
+/// from different neighbor ASes that are equal up to the MED are ordered on
+/// their neighbor AS, the lowest first, before the later steps.
+///
+/// The struct can have a `#[meta(tbi = Type)]` attribute with the type of
+/// the tiebreaker info, which is the local AS number. With a TBI, a path
+/// learned from another AS than the local one (eBGP) is preferred over a
+/// path learned from the local AS (iBGP), right after the origin. This
+/// needs a field marked `neighbor_as`, that can be compared with the TBI.
+/// Without it, the TBI type is `()`.
+///
+/// The struct can also have a `#[meta(crate = "..")]` attribute with the
+/// path to the rotonda-store crate as a string, by default
+/// `"::rotonda_store"`.
+///
+/// The other traits that `Meta` requires are not derived.
+///
+/// # Example
+/// ```ignore
+/// use rotonda_store::prelude::*;
+///
+/// #[derive(Clone, Debug, Meta)]
+/// #[meta(tbi = u32)]
+/// struct Route {
+///     #[meta(local_pref)]
+///     local_pref: u32,
+///     #[meta(as_path_len)]
+///     as_path_len: usize,
+///     #[meta(origin)]
+///     origin: u8,
+///     #[meta(neighbor_as)]
+///     neighbor_as: u32,
+///     #[meta(med)]
+///     med: u32,
+///     #[meta(bgp_id)]
+///     bgp_id: u32,
+///     #[meta(peer_addr)]
+///     peer_addr: std::net::IpAddr,
+///     next_hop: std::net::IpAddr,
+/// }
+/// ```
+#[proc_macro_derive(Meta, attributes(meta))]
+pub fn derive_meta(input: TokenStream) -> TokenStream {
+    let input = syn::parse_macro_input!(input as syn::DeriveInput);
+
+    match crate::meta::meta_impl(input) {
+        Ok(result) => TokenStream::from(result),
+        Err(err) => err.to_compile_error().into(),
+    }
 }
@@ -0,0 +1,192 @@
+// The `Meta` derive macro.
+//
+// The `Meta` trait of rotonda-store has the type for the tiebreaker info
+// (TBI) that is handed to the best path selection, and turns the meta-data
+// into an orderable value, where the greatest value is the best path. The
+// derive generates an orderable value that follows the BGP decision process
+// (RFC 4271, section 9.1.2.2) for the fields that are marked with one of:
+//
+// #[meta(local_pref)]   the highest local preference is preferred
+// #[meta(as_path_len)]  the shortest AS path is preferred
+// #[meta(origin)]       the lowest origin is preferred
+// #[meta(neighbor_as)]  the AS the path was learned from
+// #[meta(med)]          the lowest MED is preferred
+// #[meta(bgp_id)]       the lowest BGP identifier is preferred
+// #[meta(peer_addr)]    the lowest peer address is preferred
+//
+// in that order, whatever the order of the fields in the type. The type
+// itself can have `#[meta(tbi = Type)]`, with the type of the local AS
+// number as the TBI, by default the TBI is `()`, and
+// `#[meta(crate = "path")]` with the path to the rotonda-store crate.
+//
+// The MED is only comparable between paths from the same neighbor AS, which
+// an orderable value can not express, as it has to be a total order. So with
+// a `neighbor_as` field the paths are ordered on the neighbor AS before the
+// MED, and the MED only decides between paths from the same neighbor AS.
+// With a TBI a path learned from another AS than the local one (eBGP) is
+// preferred over one learned from the local AS (iBGP). This step is taken
+// before the MED step instead of after it, since it is the same for all the
+// paths from one neighbor AS.
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::spanned::Spanned;
+
+// The field markers, in the order of the BGP decision process, and whether
+// a lower value is preferred for them.
+const MARKERS: [(&str, bool); 7] = [
+    ("local_pref", false),
+    ("as_path_len", true),
+    ("origin", true),
+    ("neighbor_as", true),
+    ("med", true),
+    ("bgp_id", true),
+    ("peer_addr", true),
+];
+
+// The positions of the markers in MARKERS that the steps depend on.
+const NEIGHBOR_AS: usize = 3;
+const MED: usize = 4;
+
+pub fn meta_impl(input: syn::DeriveInput) -> syn::Result<TokenStream> {
+    let mut tbi: Option<syn::Type> = None;
+    let mut krate: Option<syn::Path> = None;
+
+    for attr in input.attrs.iter().filter(|a| a.path().is_ident("meta")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("tbi") {
+                if tbi.is_some() {
+                    return Err(meta.error("duplicate argument `tbi`"));
+                }
+                tbi = Some(meta.value()?.parse()?);
+            } else if meta.path.is_ident("crate") {
+                if krate.is_some() {
+                    return Err(meta.error("duplicate argument `crate`"));
+                }
+                krate = Some(crate::args::parse_crate_path(meta.value()?)?);
+            } else {
+                return Err(meta.error("unknown argument, expected one of `tbi`, `crate`"));
+            }
+            Ok(())
+        })?;
+    }
+
+    let krate = krate.unwrap_or_else(crate::args::default_crate);
+
+    let fields = match &input.data {
+        syn::Data::Struct(data) => &data.fields,
+        _ => {
+            return Err(syn::Error::new(
+                input.span(),
+                "`Meta` can only be derived for structs",
+            ))
+        }
+    };
+
+    // The marked field for each of the MARKERS.
+    let mut marked: [Option<(syn::Member, &syn::Type)>; 7] = Default::default();
+    let mut errors: Option<syn::Error> = None;
+    let mut push_err = |err: syn::Error| match errors.as_mut() {
+        Some(errors) => errors.combine(err),
+        None => errors = Some(err),
+    };
+
+    for (idx, field) in fields.iter().enumerate() {
+        let member = match &field.ident {
+            Some(ident) => syn::Member::Named(ident.clone()),
+            None => syn::Member::Unnamed(idx.into()),
+        };
+
+        for attr in field.attrs.iter().filter(|a| a.path().is_ident("meta")) {
+            let res = attr.parse_nested_meta(|meta| {
+                let pos = MARKERS
+                    .iter()
+                    .position(|(name, _)| meta.path.is_ident(name))
+                    .ok_or_else(|| {
+                        meta.error(
+                            "unknown field marker, expected one of \
+                            `local_pref`, `as_path_len`, `origin`, \
+                            `neighbor_as`, `med`, `bgp_id`, `peer_addr`",
+                        )
+                    })?;
+                if marked[pos].is_some() {
+                    return Err(meta.error(format!(
+                        "only one field can be marked as `{}`",
+                        MARKERS[pos].0
+                    )));
+                }
+                marked[pos] = Some((member.clone(), &field.ty));
+                Ok(())
+            });
+            if let Err(err) = res {
+                push_err(err);
+            }
+        }
+    }
+
+    // The TBI is compared with the neighbor AS.
+    if let (Some(tbi), None) = (&tbi, &marked[NEIGHBOR_AS]) {
+        push_err(syn::Error::new_spanned(
+            tbi,
+            "the TBI is the local AS, which needs a field marked `neighbor_as`",
+        ));
+    }
+
+    if let Some(errors) = errors {
+        return Err(errors);
+    }
+
+    let mut orderable_tys = vec![];
+    let mut orderables = vec![];
+
+    for (pos, (field, (_, lower_is_better))) in marked.iter().zip(MARKERS.iter()).enumerate() {
+        if pos == NEIGHBOR_AS {
+            if let (Some(_), Some((member, _))) = (&tbi, field) {
+                orderable_tys.push(quote! { bool });
+                orderables.push(quote! { self.#member != tbi });
+            }
+            // The neighbor AS only orders the paths for the MED.
+            if marked[MED].is_none() {
+                continue;
+            }
+        }
+        let (member, ty) = match field {
+            Some(field) => field,
+            None => continue,
+        };
+        if *lower_is_better {
+            orderable_tys.push(quote! { ::std::cmp::Reverse<&'a #ty> });
+            orderables.push(quote! { ::std::cmp::Reverse(&self.#member) });
+        } else {
+            orderable_tys.push(quote! { &'a #ty });
+            orderables.push(quote! { &self.#member });
+        }
+    }
+
+    // Without any marked fields all paths are equally good, and an empty
+    // body returns the unit value without tripping `clippy::unused_unit`.
+    let orderable = if orderables.is_empty() {
+        quote! {}
+    } else {
+        quote! { ( #( #orderables, )* ) }
+    };
+
+    let (tbi_ty, tbi_arg) = match &tbi {
+        Some(tbi) => (quote! { #tbi }, quote! { tbi }),
+        None => (quote! { () }, quote! { _tbi }),
+    };
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics #krate::prelude::Meta for #name #ty_generics #where_clause {
+            type Orderable<'a> = ( #( #orderable_tys, )* ) where Self: 'a;
+            type TBI = #tbi_ty;
+
+            fn as_orderable(&self, #tbi_arg: Self::TBI) -> Self::Orderable<'_> {
+                #orderable
+            }
+        }
+    })
+}
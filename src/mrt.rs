@@ -0,0 +1,189 @@
+// Export of a store in the MRT TABLE_DUMP_V2 format (RFC 6396), generated by
+// `create_store`.
+//
+// The export consists of a PEER_INDEX_TABLE record, with a peer entry for
+// each `multi_uniq_id` that has records in the store, in order of the
+// `multi_uniq_id`, followed by a RIB_IPV4_UNICAST record for each IPv4
+// prefix, and a RIB_IPV6_UNICAST record for each IPv6 prefix, with a RIB
+// entry for each record of the prefix. Withdrawn records, and records for a
+// `multi_uniq_id` that is globally withdrawn, are not exported.
+//
+// The store does not know about BGP peers or path attributes, so the peer
+// for a `multi_uniq_id` and the path attributes for a record are taken from
+// the meta-data of the records, through the `MrtMeta` trait.
+
+use proc_macro2::TokenStream;
+use quote::quote;
+
+// The MRT type and subtypes for TABLE_DUMP_V2.
+const TABLE_DUMP_V2: u16 = 13;
+const PEER_INDEX_TABLE: u16 = 1;
+const RIB_IPV4_UNICAST: u16 = 2;
+const RIB_IPV6_UNICAST: u16 = 4;
+
+// Returns the impl block with the `export_mrt_table_dump_v2` method for the
+// store. The method only exists for meta-data types that implement
+// `MrtMeta`.
+pub fn mrt_impl(krate: &syn::Path, cfgs: &TokenStream, store_name: &syn::Ident) -> TokenStream {
+    quote! {
+        #cfgs
+        impl<
+                M: #krate::prelude::Meta + #krate::prelude::multi::MrtMeta
+            > #store_name<M>
+        {
+            /// Writes the store to `writer` as an MRT TABLE_DUMP_V2 table
+            /// dump (RFC 6396), that can be read with any MRT parser.
+            ///
+            /// The dump starts with a PEER_INDEX_TABLE, with the
+            /// `collector_bgp_id` and `view_name`, and a peer entry for each
+            /// `multi_uniq_id` in the store, followed by a RIB_IPV4_UNICAST
+            /// or RIB_IPV6_UNICAST record for each prefix, with a RIB entry
+            /// for each of its records. The peers and the path attributes
+            /// are taken from the meta-data of the records, through
+            /// `MrtMeta`. Withdrawn records are left out, as are records for
+            /// a `multi_uniq_id` that is globally withdrawn.
+            ///
+            /// All MRT records, and the originated time of all RIB entries,
+            /// get `timestamp`, in seconds since the UNIX epoch, as their
+            /// time.
+            ///
+            /// Returns an error of kind `InvalidInput` if the store does not
+            /// fit in the format, e.g. if there are more than 65535 peers.
+            pub fn export_mrt_table_dump_v2<W: ::std::io::Write>(
+                &self,
+                writer: &mut W,
+                collector_bgp_id: ::std::net::Ipv4Addr,
+                view_name: &str,
+                timestamp: u32,
+            ) -> ::std::io::Result<()> {
+                fn too_large(what: &str) -> ::std::io::Error {
+                    ::std::io::Error::new(
+                        ::std::io::ErrorKind::InvalidInput,
+                        format!("too many {} for an MRT record", what)
+                    )
+                }
+
+                fn write_record<W: ::std::io::Write>(
+                    writer: &mut W,
+                    timestamp: u32,
+                    subtype: u16,
+                    body: &[u8]
+                ) -> ::std::io::Result<()> {
+                    let len = u32::try_from(body.len())
+                        .map_err(|_| too_large("bytes"))?;
+                    writer.write_all(&timestamp.to_be_bytes())?;
+                    writer.write_all(&#TABLE_DUMP_V2.to_be_bytes())?;
+                    writer.write_all(&subtype.to_be_bytes())?;
+                    writer.write_all(&len.to_be_bytes())?;
+                    writer.write_all(body)
+                }
+
+                fn push_u16(body: &mut Vec<u8>, len: usize, what: &str)
+                    -> ::std::io::Result<()>
+                {
+                    let len = u16::try_from(len).map_err(|_| too_large(what))?;
+                    body.extend_from_slice(&len.to_be_bytes());
+                    Ok(())
+                }
+
+                let is_exported = |subtype: u16, record: &#krate::prelude::Record<M>| {
+                    let mui = record.multi_uniq_id;
+                    !matches!(record.status, #krate::prelude::RouteStatus::Withdrawn)
+                        && !(if subtype == #RIB_IPV4_UNICAST {
+                            self.mui_is_withdrawn_v4(mui)
+                        } else {
+                            self.mui_is_withdrawn_v6(mui)
+                        })
+                };
+
+                // The prefixes in the store with their exported records, and
+                // the RIB subtype for their address family. The store is
+                // walked once, so that a concurrent insert cannot add a peer
+                // to the RIBs that is not in the peer index table.
+                let ribs = self.prefixes_iter_v4()
+                    .map(|p| (#RIB_IPV4_UNICAST, p))
+                    .chain(self.prefixes_iter_v6().map(|p| (#RIB_IPV6_UNICAST, p)))
+                    .filter_map(|(subtype, p_rec)| {
+                        let records = p_rec.meta
+                            .into_iter()
+                            .filter(|r| is_exported(subtype, r))
+                            .collect::<Vec<_>>();
+                        (!records.is_empty()).then(|| (subtype, p_rec.prefix, records))
+                    })
+                    .collect::<Vec<_>>();
+
+                // The peer for each multi_uniq_id, from the first exported
+                // record for it.
+                let mut peers = ::std::collections::BTreeMap::new();
+                for record in ribs.iter().flat_map(|(_, _, records)| records) {
+                    peers.entry(record.multi_uniq_id).or_insert_with(|| (
+                        record.meta.mrt_peer_bgp_id(),
+                        record.meta.mrt_peer_addr(),
+                        record.meta.mrt_peer_asn(),
+                    ));
+                }
+
+                let mut body = vec![];
+                body.extend_from_slice(&collector_bgp_id.octets());
+                push_u16(&mut body, view_name.len(), "bytes in the view name")?;
+                body.extend_from_slice(view_name.as_bytes());
+                push_u16(&mut body, peers.len(), "peers")?;
+                for (bgp_id, addr, asn) in peers.values() {
+                    // The peer type has the AS4 bit set, and the IPv6 bit
+                    // for an IPv6 peer address.
+                    match addr {
+                        ::std::net::IpAddr::V4(addr) => {
+                            body.push(0x02);
+                            body.extend_from_slice(&bgp_id.octets());
+                            body.extend_from_slice(&addr.octets());
+                        }
+                        ::std::net::IpAddr::V6(addr) => {
+                            body.push(0x03);
+                            body.extend_from_slice(&bgp_id.octets());
+                            body.extend_from_slice(&addr.octets());
+                        }
+                    }
+                    body.extend_from_slice(&asn.to_be_bytes());
+                }
+                write_record(writer, timestamp, #PEER_INDEX_TABLE, &body)?;
+
+                // The peers are taken from the same RIBs that are written
+                // below, so every record has a peer index.
+                let peer_indexes = peers
+                    .keys()
+                    .enumerate()
+                    .map(|(idx, mui)| (*mui, idx as u16))
+                    .collect::<::std::collections::BTreeMap<_, _>>();
+
+                let mut sequence_number = 0_u32;
+                for (subtype, prefix, records) in ribs {
+                    let len = prefix.len();
+                    let addr = match prefix.addr() {
+                        ::std::net::IpAddr::V4(addr) => addr.octets().to_vec(),
+                        ::std::net::IpAddr::V6(addr) => addr.octets().to_vec(),
+                    };
+
+                    let mut body = vec![];
+                    body.extend_from_slice(&sequence_number.to_be_bytes());
+                    body.push(len);
+                    body.extend_from_slice(&addr[..(len as usize).div_ceil(8)]);
+                    push_u16(&mut body, records.len(), "RIB entries")?;
+                    for record in records {
+                        let attrs = record.meta.mrt_path_attributes();
+                        body.extend_from_slice(
+                            &peer_indexes[&record.multi_uniq_id].to_be_bytes()
+                        );
+                        body.extend_from_slice(&timestamp.to_be_bytes());
+                        push_u16(&mut body, attrs.len(), "bytes in the path attributes")?;
+                        body.extend_from_slice(&attrs);
+                    }
+                    write_record(writer, timestamp, subtype, &body)?;
+
+                    sequence_number = sequence_number.wrapping_add(1);
+                }
+
+                Ok(())
+            }
+        }
+    }
+}
@@ -0,0 +1,325 @@
+// Snapshots of a store, generated by `create_store` if the `snapshot` feature
+// is enabled.
+//
+// A snapshot holds all the prefixes with their records and path selections,
+// and the globally withdrawn muis, for each address family in the store. All
+// integers are big-endian. The format, version 1, is:
+//
+// magic               4 bytes, "RTSS"
+// version             u16
+// number of families  u8
+//
+// followed by, for each address family:
+//
+// address family      u8, the number of bits, 32 or 128
+// withdrawn muis      u32 count, followed by the u32 muis
+// prefixes            for each prefix a u8 1, followed by the prefix, and
+//                     then a u8 0 after the last prefix
+//
+// and a prefix is:
+//
+// address             4 bytes for IPv4, 16 for IPv6
+// length              u8
+// best path           u8 0 for none, or a u8 1 followed by the u32 mui
+// backup path         u8 0 for none, or a u8 1 followed by the u32 mui
+// records             u32 count, followed by the records
+//
+// and a record is:
+//
+// multi_uniq_id       u32
+// ltime               u64
+// status              u8, 0 for Active, 1 for InActive, 2 for Withdrawn
+// meta                written by `SnapshotMeta::write_snapshot`
+
+use proc_macro2::{Literal, TokenStream};
+use quote::{format_ident, quote};
+
+const MAGIC: &[u8; 4] = b"RTSS";
+const VERSION: u16 = 1;
+
+// Returns the impl block with the `write_snapshot` and `read_snapshot`
+// methods for the store. The methods only exist for meta-data types that
+// implement `SnapshotMeta`.
+pub fn snapshot_impl(
+    krate: &syn::Path,
+    cfgs: &TokenStream,
+    store_name: &syn::Ident,
+    fields: &[syn::Ident],
+    ip_afs: &[syn::Ident],
+    af_types: &[TokenStream],
+) -> TokenStream {
+    let magic = Literal::byte_string(MAGIC);
+    let families = fields.len() as u8;
+    let af_bits = ip_afs
+        .iter()
+        .map(|ip_af| if ip_af == "IPv4" { 32_u8 } else { 128_u8 })
+        .collect::<Vec<_>>();
+    let addr_types = af_bits
+        .iter()
+        .map(|bits| match bits {
+            32 => format_ident!("Ipv4Addr"),
+            _ => format_ident!("Ipv6Addr"),
+        })
+        .collect::<Vec<_>>();
+    let addr_lens = af_bits
+        .iter()
+        .map(|bits| *bits as usize / 8)
+        .collect::<Vec<_>>();
+
+    quote! {
+        #cfgs
+        impl<
+                M: #krate::prelude::Meta + #krate::prelude::multi::SnapshotMeta
+            > #store_name<M>
+        {
+            /// Writes a snapshot of the store to `writer`.
+            ///
+            /// The snapshot holds all the prefixes in the store, with all
+            /// their records, whatever their status, and their stored best
+            /// and backup paths, and the `multi_uniq_id`s that are globally
+            /// withdrawn, for each address family in the store. The
+            /// meta-data of the records is written with
+            /// `SnapshotMeta::write_snapshot`.
+            ///
+            /// The store can be restored from the snapshot with
+            /// `read_snapshot`. Records that are inserted or changed while
+            /// the snapshot is written may or may not end up in the
+            /// snapshot.
+            pub fn write_snapshot<W: ::std::io::Write>(
+                &self,
+                writer: &mut W
+            ) -> ::std::io::Result<()> {
+                fn write_opt<W: ::std::io::Write>(
+                    writer: &mut W,
+                    value: Option<u32>
+                ) -> ::std::io::Result<()> {
+                    match value {
+                        Some(value) => {
+                            writer.write_all(&[1])?;
+                            writer.write_all(&value.to_be_bytes())
+                        }
+                        None => writer.write_all(&[0]),
+                    }
+                }
+
+                let guard = &#krate::prelude::multi::epoch::pin();
+
+                writer.write_all(#magic)?;
+                writer.write_all(&#VERSION.to_be_bytes())?;
+                writer.write_all(&[#families])?;
+
+                #(
+                    writer.write_all(&[#af_bits])?;
+
+                    let bmin = unsafe {
+                        self.#fields.store.withdrawn_muis_bmin.load(
+                            ::std::sync::atomic::Ordering::Acquire, guard
+                        ).deref()
+                    };
+                    writer.write_all(&(bmin.len() as u32).to_be_bytes())?;
+                    for mui in bmin.iter() {
+                        writer.write_all(&mui.to_be_bytes())?;
+                    }
+
+                    for (prefix, records) in self.#fields.store.prefixes_iter() {
+                        writer.write_all(&[1])?;
+                        match prefix.addr() {
+                            ::std::net::IpAddr::V4(addr) => writer.write_all(&addr.octets())?,
+                            ::std::net::IpAddr::V6(addr) => writer.write_all(&addr.octets())?,
+                        }
+                        writer.write_all(&[prefix.len()])?;
+
+                        let (best, backup) = self.#fields.store
+                            .non_recursive_retrieve_prefix(
+                                #krate::prelude::multi::PrefixId::<#af_types>::from(prefix),
+                            )
+                            .0
+                            .map_or((None, None), |p_rec| {
+                                let ps = unsafe { p_rec.get_path_selections(guard) };
+                                (ps.best(), ps.backup())
+                            });
+                        write_opt(writer, best)?;
+                        write_opt(writer, backup)?;
+
+                        writer.write_all(&(records.len() as u32).to_be_bytes())?;
+                        for record in records {
+                            writer.write_all(&record.multi_uniq_id.to_be_bytes())?;
+                            writer.write_all(&record.ltime.to_be_bytes())?;
+                            writer.write_all(&[match record.status {
+                                #krate::prelude::RouteStatus::Active => 0,
+                                #krate::prelude::RouteStatus::InActive => 1,
+                                #krate::prelude::RouteStatus::Withdrawn => 2,
+                            }])?;
+                            #krate::prelude::multi::SnapshotMeta::write_snapshot(
+                                &record.meta,
+                                writer
+                            )?;
+                        }
+                    }
+                    writer.write_all(&[0])?;
+                )*
+
+                Ok(())
+            }
+
+            /// Creates a new store from a snapshot that was written with
+            /// `write_snapshot`.
+            ///
+            /// The store is created with the stride sizes of this store
+            /// type, which do not have to be the same as the ones of the
+            /// store the snapshot was written from. Reading a snapshot with
+            /// prefixes for an address family this store does not have a
+            /// tree for fails.
+            pub fn read_snapshot<R: ::std::io::Read>(
+                reader: &mut R
+            ) -> ::std::io::Result<Self> {
+                fn invalid(msg: String) -> ::std::io::Error {
+                    ::std::io::Error::new(::std::io::ErrorKind::InvalidData, msg)
+                }
+
+                fn store_err(
+                    err: #krate::prelude::multi::PrefixStoreError
+                ) -> ::std::io::Error {
+                    ::std::io::Error::new(
+                        ::std::io::ErrorKind::Other,
+                        format!("{:?}", err)
+                    )
+                }
+
+                fn read_u8<R: ::std::io::Read>(
+                    reader: &mut R
+                ) -> ::std::io::Result<u8> {
+                    let mut buf = [0; 1];
+                    reader.read_exact(&mut buf)?;
+                    Ok(buf[0])
+                }
+
+                fn read_u32<R: ::std::io::Read>(
+                    reader: &mut R
+                ) -> ::std::io::Result<u32> {
+                    let mut buf = [0; 4];
+                    reader.read_exact(&mut buf)?;
+                    Ok(u32::from_be_bytes(buf))
+                }
+
+                fn read_u64<R: ::std::io::Read>(
+                    reader: &mut R
+                ) -> ::std::io::Result<u64> {
+                    let mut buf = [0; 8];
+                    reader.read_exact(&mut buf)?;
+                    Ok(u64::from_be_bytes(buf))
+                }
+
+                // Reads a u8 that is either 0 (false) or 1 (true).
+                fn read_flag<R: ::std::io::Read>(
+                    reader: &mut R
+                ) -> ::std::io::Result<bool> {
+                    match read_u8(reader)? {
+                        0 => Ok(false),
+                        1 => Ok(true),
+                        flag => Err(invalid(format!("invalid flag {} in snapshot", flag))),
+                    }
+                }
+
+                fn read_opt<R: ::std::io::Read>(
+                    reader: &mut R
+                ) -> ::std::io::Result<Option<u32>> {
+                    match read_flag(reader)? {
+                        true => read_u32(reader).map(Some),
+                        false => Ok(None),
+                    }
+                }
+
+                let mut magic = [0; 4];
+                reader.read_exact(&mut magic)?;
+                if &magic != #magic {
+                    return Err(invalid("not a snapshot of a store".to_string()));
+                }
+                let mut version = [0; 2];
+                reader.read_exact(&mut version)?;
+                let version = u16::from_be_bytes(version);
+                if version != #VERSION {
+                    return Err(invalid(format!(
+                        "unsupported snapshot version {}",
+                        version
+                    )));
+                }
+
+                let store = Self::new().map_err(|err| {
+                    ::std::io::Error::new(::std::io::ErrorKind::Other, err.to_string())
+                })?;
+                let guard = &#krate::prelude::multi::epoch::pin();
+
+                for _ in 0..read_u8(reader)? {
+                    match read_u8(reader)? {
+                        #(
+                            #af_bits => {
+                                for _ in 0..read_u32(reader)? {
+                                    store.#fields.store
+                                        .mark_mui_as_withdrawn(read_u32(reader)?, guard)
+                                        .map_err(store_err)?;
+                                }
+
+                                while read_flag(reader)? {
+                                    let mut addr = [0; #addr_lens];
+                                    reader.read_exact(&mut addr)?;
+                                    let addr = ::std::net::#addr_types::from(addr);
+                                    let len = read_u8(reader)?;
+                                    let prefix = #krate::prelude::Prefix::new(addr.into(), len)
+                                        .map_err(|_| invalid(format!(
+                                            "invalid prefix {}/{} in snapshot",
+                                            addr,
+                                            len
+                                        )))?;
+                                    let id = #krate::prelude::multi::PrefixId::<#af_types>::from(prefix);
+                                    let best = read_opt(reader)?;
+                                    let backup = read_opt(reader)?;
+
+                                    for _ in 0..read_u32(reader)? {
+                                        let mui = read_u32(reader)?;
+                                        let ltime = read_u64(reader)?;
+                                        let status = match read_u8(reader)? {
+                                            0 => #krate::prelude::RouteStatus::Active,
+                                            1 => #krate::prelude::RouteStatus::InActive,
+                                            2 => #krate::prelude::RouteStatus::Withdrawn,
+                                            status => return Err(invalid(format!(
+                                                "invalid record status {} in snapshot",
+                                                status
+                                            ))),
+                                        };
+                                        let meta = <M as #krate::prelude::multi::SnapshotMeta>::read_snapshot(reader)?;
+                                        store.status_changes.seen(ltime);
+                                        store.#fields.insert(
+                                            id,
+                                            #krate::prelude::Record::new(mui, ltime, status, meta),
+                                            None
+                                        ).map_err(store_err)?;
+                                    }
+
+                                    if best.is_some() || backup.is_some() {
+                                        if let Some(p_rec) = store.#fields.store
+                                            .non_recursive_retrieve_prefix(id)
+                                            .0
+                                        {
+                                            p_rec.set_path_selections(best, backup, guard)
+                                                .map_err(store_err)?;
+                                        }
+                                    }
+                                }
+                            }
+                        )*
+                        af_bits => {
+                            return Err(invalid(format!(
+                                "snapshot has prefixes for an address family with \
+                                {} bits, that the store does not have a tree for",
+                                af_bits
+                            )))
+                        }
+                    }
+                }
+
+                Ok(store)
+            }
+        }
+    }
+}
@@ -0,0 +1,293 @@
+// Validation of the stride sizes handed to the `stride_sizes` macro.
+//
+// The tree in a store is made up of nodes that each cover a number of bits
+// of the address, the stride. The sequence of stride sizes for an address
+// family should cover all the bits in the address exactly, so for IPv4 the
+// strides should add up to 32, and for IPv6 to 128. Each stride size needs
+// to be one of the sizes that the store has a node type for.
+//
+// All of this is checked at compile time. Every problem found is reported as
+// a `syn::Error` that points at the offending element in the array, so that
+// the user gets all the diagnostics for a stride array in one go.
+//
+// A proc macro cannot evaluate a const, so a named const array of stride
+// sizes can only be used as a macro argument if it is defined with the
+// `strides!` macro. Next to the const itself, `strides!` defines a
+// `macro_rules!` macro that is imported under the name of the const, so it
+// has the same path, and that expands to an item annotated with an
+// attribute macro that has the stride sizes as an array literal in its
+// arguments. The `stride_sizes` macro, when handed the path of a const,
+// hands itself and the item to this macro, e.g.:
+//
+// IP4_STRIDES! {
+//     { ::rotonda_store::prelude::multi::stride_sizes }
+//     (crate = "::rotonda_store", IPv4,)
+//     struct NuStoreIPv4;
+// }
+//
+// expands to:
+//
+// #[::rotonda_store::prelude::multi::stride_sizes(
+//     crate = "::rotonda_store", IPv4, [5, 5, 4, 3, 3, 3, 3, 3, 3]
+// )]
+// struct NuStoreIPv4;
+
+use proc_macro2::Span;
+use syn::parse::{Parse, ParseStream};
+use syn::spanned::Spanned;
+use syn::Token;
+
+// The stride sizes the store has node types for. The `stride_sizes` macro
+// generates a `get_store<N>` accessor for each of these, which have to be
+// methods of the `NodeBuckets` trait of rotonda-store. rotonda-store has
+// node types for the stride sizes 3, 4 and 5, the other ones are behind the
+// `extra-strides` feature, until rotonda-store has node types for them.
+#[cfg(not(feature = "extra-strides"))]
+pub const STRIDE_SIZES: &[u8] = &[3, 4, 5];
+#[cfg(feature = "extra-strides")]
+pub const STRIDE_SIZES: &[u8] = &[1, 2, 3, 4, 5, 6, 7, 8];
+
+// All the stride sizes there are node types for with the `extra-strides`
+// feature.
+const EXTRA_STRIDE_SIZES: std::ops::RangeInclusive<u8> = 1..=8;
+
+// The maximum number of strides for one address family. This is the size
+// of the `stride_sizes` buffer in the generated NodeBuckets struct. For
+// IPv6 this means that the strides have to be at least 128 / 42 bits wide
+// on average, so strides of 1 or 2 bits can only be used together with
+// wider ones, e.g. `[2; 64]` is too many strides.
+pub const MAX_STRIDES: usize = 42;
+
+// Returns the number of bits in the address family indicated by `af`,
+// either `IPv4` or `IPv6`.
+pub fn af_bits(af: &syn::Expr) -> syn::Result<u8> {
+    match af {
+        syn::Expr::Path(p) if p.path.is_ident("IPv4") => Ok(32),
+        syn::Expr::Path(p) if p.path.is_ident("IPv6") => Ok(128),
+        _ => Err(syn::Error::new(
+            af.span(),
+            "expected an address family, either `IPv4` or `IPv6`",
+        )),
+    }
+}
+
+// Parses and validates the array of stride sizes for an address family
+// with `af_bits` bits, and returns the stride sizes as u8s.
+//
+// If the strides in the array add up to less than `af_bits`, the array is
+// used as a pattern that is repeated until the strides add up to `af_bits`,
+// so `[4]` for IPv4 results in `[4, 4, 4, 4, 4, 4, 4, 4]`, and
+// `[5, 5, 4, 3]` for IPv6 results in `[5, 5, 4, 3, 5, 5, 4, 3, ...]`. The
+// repetition has to end exactly at `af_bits`.
+pub fn parse_strides(strides: &syn::Expr, af_bits: u8) -> syn::Result<Vec<u8>> {
+    let pattern = parse_pattern(strides)?;
+
+    let mut strides_num = vec![];
+    let mut sum = 0_u16;
+
+    for (idx, (stride_len, span)) in pattern.iter().cycle().enumerate() {
+        let repeated = idx >= pattern.len();
+        if repeated && sum == af_bits as u16 {
+            break;
+        }
+
+        if sum + *stride_len as u16 > af_bits as u16 {
+            let msg = if repeated {
+                format!(
+                    "repeating the strides does not add up to exactly the \
+                    {} bits of the address family: the strides add up to {} \
+                    before, and to {} after this stride",
+                    af_bits,
+                    sum,
+                    sum + *stride_len as u16
+                )
+            } else {
+                format!(
+                    "strides add up to {} at this stride, which exceeds the \
+                    {} bits of the address family",
+                    sum + *stride_len as u16,
+                    af_bits
+                )
+            };
+            return Err(syn::Error::new(*span, msg));
+        }
+
+        if idx == MAX_STRIDES {
+            let (span, msg) = if repeated {
+                (
+                    strides.span(),
+                    format!(
+                        "too many strides: repeating the strides results in \
+                        more than {} strides",
+                        MAX_STRIDES
+                    ),
+                )
+            } else {
+                (
+                    *span,
+                    format!(
+                        "too many strides: at most {} strides are supported",
+                        MAX_STRIDES
+                    ),
+                )
+            };
+            return Err(syn::Error::new(span, msg));
+        }
+
+        sum += *stride_len as u16;
+        strides_num.push(*stride_len);
+    }
+
+    Ok(strides_num)
+}
+
+// Parses the stride sizes as written in `strides`, without repeating them.
+//
+// `strides` can be an array of stride sizes, e.g. `[5, 5, 4, 3]`, or a
+// repeat expression, e.g. `[4; 8]`. The returned stride sizes carry the span
+// of the element they were parsed from.
+pub fn parse_pattern(strides: &syn::Expr) -> syn::Result<Vec<(u8, Span)>> {
+    match strides {
+        syn::Expr::Array(arr) => {
+            if arr.elems.is_empty() {
+                return Err(syn::Error::new(
+                    arr.span(),
+                    "expected at least one stride size",
+                ));
+            }
+
+            let mut errors: Option<syn::Error> = None;
+            let mut pattern = vec![];
+            for stride in arr.elems.iter() {
+                match parse_stride(stride) {
+                    Ok(stride_len) => pattern.push((stride_len, stride.span())),
+                    Err(err) => match errors.as_mut() {
+                        Some(errors) => errors.combine(err),
+                        None => errors = Some(err),
+                    },
+                }
+            }
+
+            match errors {
+                Some(errors) => Err(errors),
+                None => Ok(pattern),
+            }
+        }
+        syn::Expr::Repeat(rep) => {
+            let stride_len = parse_stride(&rep.expr)?;
+            let count = match &*rep.len {
+                syn::Expr::Lit(syn::ExprLit {
+                    lit: syn::Lit::Int(lit),
+                    ..
+                }) => lit.base10_parse::<usize>()?,
+                len => {
+                    return Err(syn::Error::new(
+                        len.span(),
+                        "expected an integer literal as the number of strides",
+                    ))
+                }
+            };
+            // The count is checked before the strides are allocated, so
+            // that a huge count is an error instead of running out of
+            // memory.
+            if count == 0 {
+                return Err(syn::Error::new(
+                    rep.len.span(),
+                    "expected at least one stride size",
+                ));
+            }
+            if count > MAX_STRIDES {
+                return Err(syn::Error::new(
+                    rep.len.span(),
+                    format!(
+                        "too many strides: at most {} strides are supported",
+                        MAX_STRIDES
+                    ),
+                ));
+            }
+            Ok(vec![(stride_len, rep.expr.span()); count])
+        }
+        _ => Err(syn::Error::new(
+            strides.span(),
+            "expected an array of stride sizes, e.g. `[5, 5, 4, 3]` or \
+            `[4; 8]`, or a const defined with `strides!`",
+        )),
+    }
+}
+
+// Parses one stride size, an integer literal that is one of the supported
+// STRIDE_SIZES. The errors do not depend on the `extra-strides` feature,
+// other than for a stride size that needs it.
+fn parse_stride(stride: &syn::Expr) -> syn::Result<u8> {
+    let lit = match stride {
+        syn::Expr::Lit(syn::ExprLit {
+            lit: syn::Lit::Int(lit),
+            ..
+        }) => lit,
+        _ => {
+            return Err(syn::Error::new(
+                stride.span(),
+                "expected an integer literal as stride size",
+            ))
+        }
+    };
+
+    match lit.base10_parse::<u8>() {
+        Ok(stride_len) if STRIDE_SIZES.contains(&stride_len) => Ok(stride_len),
+        Ok(stride_len) if EXTRA_STRIDE_SIZES.contains(&stride_len) => Err(syn::Error::new(
+            lit.span(),
+            format!(
+                "stride size {} needs the `extra-strides` feature of \
+                rotonda-macros, and node types for it in rotonda-store",
+                stride_len
+            ),
+        )),
+        _ => Err(syn::Error::new(
+            lit.span(),
+            "expected a stride size of 3, 4 or 5, or from 1 up to and \
+            including 8 with the `extra-strides` feature",
+        )),
+    }
+}
+
+// The input for the `strides!` macro: one or more const items without a
+// type, e.g. `pub const IP4_STRIDES = [5, 5, 4, 3, 3, 3, 3, 3, 3];`.
+pub struct StridesConsts(pub Vec<StridesConst>);
+
+pub struct StridesConst {
+    pub attrs: Vec<syn::Attribute>,
+    pub vis: syn::Visibility,
+    pub ident: syn::Ident,
+    // The stride sizes with the spans of the elements they were parsed
+    // from, so that errors for the const point at its definition.
+    pub strides: Vec<(u8, Span)>,
+}
+
+impl Parse for StridesConsts {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut consts = vec![];
+        while !input.is_empty() {
+            consts.push(input.parse()?);
+        }
+        Ok(StridesConsts(consts))
+    }
+}
+
+impl Parse for StridesConst {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let attrs = input.call(syn::Attribute::parse_outer)?;
+        let vis = input.parse()?;
+        input.parse::<Token![const]>()?;
+        let ident = input.parse()?;
+        input.parse::<Token![=]>()?;
+        let strides = input.parse::<syn::Expr>()?;
+        input.parse::<Token![;]>()?;
+
+        Ok(StridesConst {
+            attrs,
+            vis,
+            ident,
+            strides: parse_pattern(&strides)?,
+        })
+    }
+}
@@ -0,0 +1,184 @@
+// Snapshot tests of the code generated by the macros.
+//
+// The expanded code is formatted with prettyplease and compared to the
+// snapshots in src/snapshots, so that every change of the generated code
+// shows up in review. After a deliberate change, update the snapshots with
+// `cargo insta review`, or with `INSTA_UPDATE=always cargo test`.
+//
+// Whether the generated code compiles is checked in tests/stores.rs.
+
+use quote::quote;
+
+fn pretty(tokens: proc_macro2::TokenStream) -> String {
+    prettyplease::unparse(&syn::parse2(tokens).unwrap())
+}
+
+// The accessors for the NodeSets depend on the `extra-strides` feature, the
+// stride sizes it adds are checked separately.
+#[cfg(not(feature = "extra-strides"))]
+#[test]
+fn stride_sizes_ipv4() {
+    insta::assert_snapshot!(pretty(crate::expand_stride_sizes(
+        quote! { IPv4, [5, 5, 4, 3, 3, 3, 3, 3, 3], crate = "crate" },
+        quote! { struct NuStoreIPv4; },
+    )));
+}
+
+#[cfg(not(feature = "extra-strides"))]
+#[test]
+fn stride_sizes_ipv6() {
+    insta::assert_snapshot!(pretty(crate::expand_stride_sizes(
+        quote! { (IPv6, [5, 5, 3, 3]) },
+        quote! { struct NuStoreIPv6; },
+    )));
+}
+
+#[test]
+fn stride_sizes_const() {
+    insta::assert_snapshot!(pretty(crate::expand_stride_sizes(
+        quote! { IPv4, IP4_STRIDES },
+        quote! { struct NuStoreIPv4; },
+    )));
+}
+
+#[test]
+fn strides() {
+    insta::assert_snapshot!(pretty(crate::expand_strides(quote! {
+        /// The stride sizes for IPv4.
+        pub const IP4_STRIDES = [4; 8];
+        const IP6_STRIDES = [5, 5, 3, 3];
+    })));
+}
+
+// The store gets more methods with the `mrt` and `snapshot` features, these
+// are checked separately.
+#[cfg(not(any(feature = "extra-strides", feature = "mrt", feature = "snapshot")))]
+#[test]
+fn create_store() {
+    insta::assert_snapshot!(pretty(crate::expand_create_store(
+        quote! { v4 = [4; 8], v6 = [5, 5, 3, 3] },
+        quote! {
+            /// The store.
+            #[derive(Debug)]
+            pub struct NuStore;
+        },
+    )));
+}
+
+#[cfg(not(any(feature = "extra-strides", feature = "mrt", feature = "snapshot")))]
+#[test]
+fn create_store_v4_only() {
+    insta::assert_snapshot!(pretty(crate::expand_create_store(
+        quote! { v6 = None, crate = "crate" },
+        quote! {
+            #[cfg(feature = "store")]
+            pub(crate) struct NuStore;
+        },
+    )));
+}
+
+#[cfg(feature = "mrt")]
+#[test]
+fn mrt_impl() {
+    insta::assert_snapshot!(pretty(crate::mrt::mrt_impl(
+        &crate::args::default_crate(),
+        &quote! {},
+        &quote::format_ident!("NuStore"),
+    )));
+}
+
+#[cfg(feature = "snapshot")]
+#[test]
+fn snapshot_impl() {
+    let krate = crate::args::default_crate();
+    insta::assert_snapshot!(pretty(crate::snapshot::snapshot_impl(
+        &krate,
+        &quote! {},
+        &quote::format_ident!("NuStore"),
+        &[quote::format_ident!("v4"), quote::format_ident!("v6")],
+        &[quote::format_ident!("IPv4"), quote::format_ident!("IPv6")],
+        &[
+            quote! { #krate::prelude::IPv4 },
+            quote! { #krate::prelude::IPv6 },
+        ],
+    )));
+}
+
+#[test]
+fn derive_meta() {
+    insta::assert_snapshot!(pretty(
+        crate::meta::meta_impl(syn::parse_quote! {
+            #[meta(tbi = u32, crate = "crate")]
+            struct Route<T> {
+                #[meta(med)]
+                med: u32,
+                #[meta(peer_addr)]
+                peer_addr: ::std::net::IpAddr,
+                #[meta(neighbor_as)]
+                neighbor_as: u32,
+                #[meta(bgp_id)]
+                bgp_id: u32,
+                #[meta(local_pref)]
+                local_pref: u32,
+                #[meta(origin)]
+                origin: u8,
+                #[meta(as_path_len)]
+                as_path_len: usize,
+                next_hop: T,
+            }
+        })
+        .unwrap()
+    ));
+}
+
+// The default bucket map has the same levels as the arithmetic that was
+// generated before the bucket maps were tables.
+#[test]
+fn default_bucket_map() {
+    let map = crate::maps::BucketMap::default();
+    for af_bits in [32_u8, 128] {
+        for nodes in [true, false] {
+            for (len, row) in map.rows(af_bits, nodes).iter().enumerate() {
+                for lvl in 0..=af_bits {
+                    let res = 4 * (lvl as usize + 1);
+                    let expected = if res < len {
+                        res
+                    } else if res >= len + 4 {
+                        0
+                    } else {
+                        len
+                    };
+                    let end = row.get(lvl as usize).copied().unwrap_or(0);
+                    assert_eq!(end as usize, expected, "len {} lvl {}", len, lvl);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(not(feature = "extra-strides"))]
+#[test]
+fn stride_sizes_bucket_map() {
+    insta::assert_snapshot!(pretty(crate::expand_stride_sizes(
+        quote! { IPv4, [4; 8], bucket_map = legacy },
+        quote! { struct NuStoreIPv4; },
+    )));
+}
+
+#[cfg(feature = "extra-strides")]
+#[test]
+fn stride_sizes_extra_strides() {
+    insta::assert_snapshot!(pretty(crate::expand_stride_sizes(
+        quote! { IPv4, [1, 2, 6, 7, 8, 8] },
+        quote! { struct NuStoreIPv4; },
+    )));
+}
+
+// The default strides cover the address bits of their address family.
+#[test]
+fn default_strides() {
+    let v4 = crate::strides::parse_strides(&crate::args::default_v4(), 32).unwrap();
+    let v6 = crate::strides::parse_strides(&crate::args::default_v6(), 128).unwrap();
+    assert_eq!(v4.iter().map(|s| *s as u32).sum::<u32>(), 32);
+    assert_eq!(v6.iter().map(|s| *s as u32).sum::<u32>(), 128);
+}
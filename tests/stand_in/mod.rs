@@ -0,0 +1,811 @@
+// A stand-in for the parts of the rotonda-store API that the code generated
+// by the macros uses.
+//
+// The macros generate code that only compiles inside, or next to,
+// rotonda-store, which depends on this crate. The stand-in has the same
+// types, traits and methods, with the same signatures, so that the generated
+// code can be compiled in the tests of this crate. It is used as the
+// rotonda-store crate through the `crate = "crate::stand_in"` argument of the
+// macros.
+//
+// The store itself is a simple in-memory implementation: the prefixes are
+// kept in a map behind a mutex, and the records and path selections are
+// never freed before the store is dropped, so that references to them can be
+// handed out like rotonda-store does with its epoch-based reclamation. This
+// is enough to test the behaviour of the generated methods, not the
+// behaviour of rotonda-store. The iterators of the stand-in return the
+// prefixes in descending order, so that tests do not accidentally depend on
+// an order that rotonda-store does not guarantee.
+//
+// The items marked with "Not in rotonda-store yet" are needed by the
+// generated code, but have to be added to rotonda-store, see the Changelog.
+//
+// Keep it in sync with rotonda-store when the generated code starts using
+// more of its API.
+
+#![allow(dead_code, unused, clippy::all)]
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::marker::PhantomData;
+use std::net::IpAddr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+pub mod prelude {
+    pub use super::{
+        AddressFamily, IPv4, IPv6, MatchOptions, MatchType, Meta, Prefix, PrefixRecord,
+        QueryResult, Record, RecordSet, RouteStatus,
+    };
+    pub mod multi {
+        pub use super::super::epoch::{self, Guard};
+        pub use super::super::{
+            BatchReport, MrtMeta, MuiReport, MuiStatus, NodeBuckets, NodeSet, PrefixBuckets,
+            PrefixId, PrefixSet, PrefixStoreError, RecalculationReport, SnapshotMeta, StoreStats,
+            Stride1, Stride2, Stride3, Stride4, Stride5, Stride6, Stride7, Stride8, StrideNodeId,
+            TreeBitMap, UpsertReport,
+        };
+        pub use rotonda_macros::Meta;
+        pub use rotonda_macros::{create_store, stride_sizes, strides};
+    }
+}
+
+pub mod epoch {
+    pub struct Guard;
+    pub fn pin() -> Guard {
+        Guard
+    }
+    pub struct Atomic<T>(pub T);
+    pub struct Shared<'g, T>(&'g T);
+    impl<T> Atomic<T> {
+        pub fn load<'g>(&'g self, _: std::sync::atomic::Ordering, _: &'g Guard) -> Shared<'g, T> {
+            Shared(&self.0)
+        }
+    }
+    impl<'g, T> Shared<'g, T> {
+        pub unsafe fn deref(&self) -> &'g T {
+            self.0
+        }
+    }
+}
+
+pub trait AddressFamily: Copy + Ord + std::fmt::Debug + 'static {
+    const BITS: u8;
+    fn from_ipaddr(addr: IpAddr) -> Self;
+    fn into_ipaddr(self) -> IpAddr;
+    fn into_u128(self) -> u128;
+}
+pub type IPv4 = u32;
+pub type IPv6 = u128;
+impl AddressFamily for u32 {
+    const BITS: u8 = 32;
+    fn from_ipaddr(addr: IpAddr) -> Self {
+        match addr {
+            IpAddr::V4(addr) => addr.into(),
+            IpAddr::V6(_) => panic!("not an IPv4 address"),
+        }
+    }
+    fn into_ipaddr(self) -> IpAddr {
+        std::net::Ipv4Addr::from(self).into()
+    }
+    fn into_u128(self) -> u128 {
+        self.into()
+    }
+}
+impl AddressFamily for u128 {
+    const BITS: u8 = 128;
+    fn from_ipaddr(addr: IpAddr) -> Self {
+        match addr {
+            IpAddr::V6(addr) => addr.into(),
+            IpAddr::V4(_) => panic!("not an IPv6 address"),
+        }
+    }
+    fn into_ipaddr(self) -> IpAddr {
+        std::net::Ipv6Addr::from(self).into()
+    }
+    fn into_u128(self) -> u128 {
+        self
+    }
+}
+pub trait Meta: Clone {
+    type Orderable<'a>: Ord
+    where
+        Self: 'a;
+    type TBI: Clone;
+    fn as_orderable(&self, tbi: Self::TBI) -> Self::Orderable<'_>;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Prefix(IpAddr, u8);
+impl Prefix {
+    pub fn new(addr: IpAddr, len: u8) -> Result<Self, PrefixError> {
+        Ok(Prefix(addr, len))
+    }
+    pub fn addr(&self) -> IpAddr {
+        self.0
+    }
+    pub fn len(&self) -> u8 {
+        self.1
+    }
+}
+#[derive(Debug)]
+pub struct PrefixError;
+
+#[derive(Debug)]
+pub struct NodeSet<AF, S>(PhantomData<(AF, S)>);
+impl<AF, S> NodeSet<AF, S> {
+    pub fn init(_: u8) -> Self {
+        NodeSet(PhantomData)
+    }
+}
+#[derive(Debug)]
+pub struct PrefixSet<AF, M>(PhantomData<(AF, M)>);
+impl<AF, M> PrefixSet<AF, M> {
+    pub fn init(_: usize) -> Self {
+        PrefixSet(PhantomData)
+    }
+}
+macro_rules! stride_types { ($($s:ident),*) => { $( #[derive(Debug)] pub struct $s; )* } }
+// Not in rotonda-store yet: Stride1, Stride2, Stride6, Stride7 and Stride8.
+stride_types!(Stride1, Stride2, Stride3, Stride4, Stride5, Stride6, Stride7, Stride8);
+#[derive(Debug, Clone, Copy)]
+pub struct StrideNodeId<AF>(AF, u8);
+impl<AF: Copy> StrideNodeId<AF> {
+    pub fn get_id(&self) -> (AF, u8) {
+        (self.0, self.1)
+    }
+}
+impl<AF: std::fmt::Debug> std::fmt::Display for StrideNodeId<AF> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{:?}", self.0)
+    }
+}
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct PrefixId<AF>(AF, u8);
+impl<AF> PrefixId<AF> {
+    pub fn new(af: AF, len: u8) -> Self {
+        PrefixId(af, len)
+    }
+    pub fn get_len(&self) -> u8 {
+        self.1
+    }
+}
+impl<AF: AddressFamily> PrefixId<AF> {
+    pub fn into_pub(&self) -> Prefix {
+        Prefix(self.0.into_ipaddr(), self.1)
+    }
+    // Whether `other` is a more-specific of this prefix, or this prefix
+    // itself.
+    fn covers(&self, other: &Self) -> bool {
+        if self.1 > other.1 {
+            return false;
+        }
+        if self.1 == 0 {
+            return true;
+        }
+        let shift = AF::BITS - self.1;
+        self.0.into_u128() >> shift == other.0.into_u128() >> shift
+    }
+}
+impl<AF: AddressFamily> From<Prefix> for PrefixId<AF> {
+    fn from(prefix: Prefix) -> Self {
+        PrefixId(AF::from_ipaddr(prefix.addr()), prefix.len())
+    }
+}
+pub trait NodeBuckets<AF> {
+    fn init() -> Self;
+    // Not in rotonda-store yet: get_store1, get_store2, get_store6,
+    // get_store7 and get_store8, for the `extra-strides` feature.
+    #[cfg(feature = "extra-strides")]
+    fn get_store1(&self, id: StrideNodeId<AF>) -> &NodeSet<AF, Stride1>;
+    #[cfg(feature = "extra-strides")]
+    fn get_store2(&self, id: StrideNodeId<AF>) -> &NodeSet<AF, Stride2>;
+    fn get_store3(&self, id: StrideNodeId<AF>) -> &NodeSet<AF, Stride3>;
+    fn get_store4(&self, id: StrideNodeId<AF>) -> &NodeSet<AF, Stride4>;
+    fn get_store5(&self, id: StrideNodeId<AF>) -> &NodeSet<AF, Stride5>;
+    #[cfg(feature = "extra-strides")]
+    fn get_store6(&self, id: StrideNodeId<AF>) -> &NodeSet<AF, Stride6>;
+    #[cfg(feature = "extra-strides")]
+    fn get_store7(&self, id: StrideNodeId<AF>) -> &NodeSet<AF, Stride7>;
+    #[cfg(feature = "extra-strides")]
+    fn get_store8(&self, id: StrideNodeId<AF>) -> &NodeSet<AF, Stride8>;
+    fn get_stride_sizes(&self) -> &[u8];
+    fn get_stride_for_id(&self, id: StrideNodeId<AF>) -> u8;
+    fn len_to_store_bits(len: u8, lvl: u8) -> u8;
+    fn get_strides_len() -> u8;
+    fn get_first_stride_size() -> u8;
+}
+pub trait PrefixBuckets<AF, M> {
+    fn init() -> Self;
+    fn remove(&mut self, id: PrefixId<AF>) -> Option<M>;
+    fn get_root_prefix_set(&self, len: u8) -> &'_ PrefixSet<AF, M>;
+    fn get_bits_for_len(len: u8, lvl: u8) -> u8;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RouteStatus {
+    Active,
+    InActive,
+    Withdrawn,
+}
+#[derive(Debug, Clone)]
+pub struct Record<M> {
+    pub multi_uniq_id: u32,
+    pub ltime: u64,
+    pub status: RouteStatus,
+    pub meta: M,
+}
+impl<M> Record<M> {
+    pub fn new(multi_uniq_id: u32, ltime: u64, status: RouteStatus, meta: M) -> Self {
+        Record {
+            multi_uniq_id,
+            ltime,
+            status,
+            meta,
+        }
+    }
+}
+#[derive(Debug, Clone)]
+pub struct PrefixRecord<M> {
+    pub prefix: Prefix,
+    pub meta: Vec<Record<M>>,
+}
+impl<M> From<(Prefix, Vec<Record<M>>)> for PrefixRecord<M> {
+    fn from(p: (Prefix, Vec<Record<M>>)) -> Self {
+        PrefixRecord {
+            prefix: p.0,
+            meta: p.1,
+        }
+    }
+}
+#[derive(Debug)]
+pub struct RecordSet<M>(pub Vec<PrefixRecord<M>>);
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MatchType {
+    ExactMatch,
+    LongestMatch,
+    EmptyMatch,
+}
+pub struct MatchOptions {
+    pub match_type: MatchType,
+    pub include_withdrawn: bool,
+    pub include_less_specifics: bool,
+    pub include_more_specifics: bool,
+    pub mui: Option<u32>,
+}
+pub struct QueryResult<M> {
+    pub match_type: MatchType,
+    pub prefix: Option<Prefix>,
+    pub prefix_meta: Vec<Record<M>>,
+    pub less_specifics: Option<RecordSet<M>>,
+    pub more_specifics: Option<RecordSet<M>>,
+}
+#[derive(Debug, PartialEq, Eq)]
+pub enum PrefixStoreError {
+    StoreNotReadyError,
+    BestPathNotFound,
+    PrefixNotFound,
+    // Not in rotonda-store yet.
+    UnsupportedAddressFamily,
+}
+#[derive(Debug)]
+pub struct UpsertReport {
+    pub cas_count: usize,
+    pub prefix_new: bool,
+    pub mui_new: bool,
+    pub mui_count: usize,
+}
+#[derive(Debug, Default)]
+pub struct CreatedNodes;
+// Not in rotonda-store yet.
+#[derive(Debug)]
+pub struct MuiStatus {
+    pub v4: Option<RouteStatus>,
+    pub v6: Option<RouteStatus>,
+}
+// Not in rotonda-store yet.
+#[derive(Debug)]
+pub struct MuiReport {
+    pub v4: Option<Result<(), PrefixStoreError>>,
+    pub v6: Option<Result<(), PrefixStoreError>>,
+}
+// Not in rotonda-store yet.
+#[derive(Debug, Default)]
+pub struct BatchReport {
+    pub prefixes_new: usize,
+    pub records_new: usize,
+    pub records_replaced: usize,
+    pub cas_count: usize,
+}
+// Not in rotonda-store yet.
+#[derive(Debug, Default)]
+pub struct RecalculationReport {
+    pub prefixes_checked: usize,
+    pub prefixes_recalculated: usize,
+    pub best_changed: usize,
+    pub backup_changed: usize,
+}
+#[derive(Debug)]
+pub struct StoreStats {
+    pub v4: Vec<CreatedNodes>,
+    pub v6: Vec<CreatedNodes>,
+}
+pub struct Counters;
+impl Counters {
+    pub fn get_prefix_stats(&self) -> Vec<CreatedNodes> {
+        vec![]
+    }
+}
+#[derive(Default)]
+pub struct Bitmap(Mutex<BTreeSet<u32>>);
+impl Bitmap {
+    pub fn contains(&self, mui: u32) -> bool {
+        self.0.lock().unwrap().contains(&mui)
+    }
+    pub fn len(&self) -> u64 {
+        self.0.lock().unwrap().len() as u64
+    }
+    pub fn iter(&self) -> impl Iterator<Item = u32> + '_ {
+        self.0.lock().unwrap().clone().into_iter()
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct PathSelections {
+    best: Option<u32>,
+    backup: Option<u32>,
+}
+impl PathSelections {
+    pub fn best(&self) -> Option<u32> {
+        self.best
+    }
+    pub fn backup(&self) -> Option<u32> {
+        self.backup
+    }
+}
+
+// Hands out a reference to a boxed value that lives as long as `owner`,
+// because the boxes are only ever added to, never removed from, the
+// collections in the stand-in.
+unsafe fn extend<'a, T>(value: &T, _owner: &'a impl Sized) -> &'a T {
+    &*(value as *const T)
+}
+
+// The records for the `multi_uniq_id`s of a prefix. Every change adds a new
+// version of the record.
+pub struct MultiMap<M> {
+    versions: Mutex<Vec<Box<Record<M>>>>,
+    current: Mutex<BTreeMap<u32, usize>>,
+}
+impl<M: Meta> MultiMap<M> {
+    fn new() -> Self {
+        MultiMap {
+            versions: Mutex::new(vec![]),
+            current: Mutex::new(BTreeMap::new()),
+        }
+    }
+    // Not in rotonda-store yet.
+    pub fn iter_records<'g>(
+        &'g self,
+        mui: Option<u32>,
+        _: &'g epoch::Guard,
+    ) -> impl Iterator<Item = &'g Record<M>> + 'g {
+        let versions = self.versions.lock().unwrap();
+        self.current
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|(m, _)| mui.map_or(true, |mui| mui == **m))
+            .map(|(_, idx)| unsafe { extend(&*versions[*idx], self) })
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+    pub fn get_record_for_active_mui(&self, mui: u32) -> Option<Record<M>> {
+        self.record(mui)
+            .filter(|r| r.status != RouteStatus::Withdrawn)
+    }
+    fn record(&self, mui: u32) -> Option<Record<M>> {
+        let versions = self.versions.lock().unwrap();
+        let idx = *self.current.lock().unwrap().get(&mui)?;
+        Some((*versions[idx]).clone())
+    }
+    fn records(&self) -> Vec<Record<M>> {
+        let versions = self.versions.lock().unwrap();
+        self.current
+            .lock()
+            .unwrap()
+            .values()
+            .map(|idx| (*versions[*idx]).clone())
+            .collect()
+    }
+    // Adds or replaces the record for its mui, and returns whether the mui
+    // is new.
+    fn upsert(&self, record: Record<M>) -> bool {
+        let mut versions = self.versions.lock().unwrap();
+        let mui = record.multi_uniq_id;
+        versions.push(Box::new(record));
+        self.current
+            .lock()
+            .unwrap()
+            .insert(mui, versions.len() - 1)
+            .is_none()
+    }
+    fn set_status(&self, mui: u32, status: RouteStatus) -> Result<(), PrefixStoreError> {
+        let mut record = self.record(mui).ok_or(PrefixStoreError::PrefixNotFound)?;
+        record.status = status;
+        self.upsert(record);
+        Ok(())
+    }
+    fn len(&self) -> usize {
+        self.current.lock().unwrap().len()
+    }
+}
+pub struct StoredPrefix<AF, M> {
+    pub prefix: PrefixId<AF>,
+    pub record_map: MultiMap<M>,
+    path_selections: Mutex<Vec<Box<PathSelections>>>,
+    ps_outdated: AtomicBool,
+}
+impl<AF, M: Meta> StoredPrefix<AF, M> {
+    fn new(prefix: PrefixId<AF>) -> Self {
+        StoredPrefix {
+            prefix,
+            record_map: MultiMap::new(),
+            path_selections: Mutex::new(vec![Box::default()]),
+            ps_outdated: AtomicBool::new(true),
+        }
+    }
+    pub unsafe fn get_path_selections<'g>(&'g self, _: &'g epoch::Guard) -> &'g PathSelections {
+        let path_selections = self.path_selections.lock().unwrap();
+        extend(&**path_selections.last().unwrap(), self)
+    }
+    pub fn calculate_and_store_best_backup(
+        &self,
+        tbi: &M::TBI,
+        guard: &epoch::Guard,
+    ) -> Result<(Option<u32>, Option<u32>), PrefixStoreError> {
+        let mut records = self
+            .record_map
+            .records()
+            .into_iter()
+            .filter(|r| r.status == RouteStatus::Active)
+            .collect::<Vec<_>>();
+        records.sort_by(|a, b| {
+            b.meta
+                .as_orderable(tbi.clone())
+                .cmp(&a.meta.as_orderable(tbi.clone()))
+                .then(a.multi_uniq_id.cmp(&b.multi_uniq_id))
+        });
+        let mut muis = records.iter().map(|r| r.multi_uniq_id);
+        let (best, backup) = (muis.next(), muis.next());
+        self.set_path_selections(best, backup, guard)?;
+        Ok((best, backup))
+    }
+    pub fn is_ps_outdated(&self, _: &epoch::Guard) -> bool {
+        self.ps_outdated.load(Ordering::Acquire)
+    }
+    // Not in rotonda-store yet.
+    pub fn set_path_selections(
+        &self,
+        best: Option<u32>,
+        backup: Option<u32>,
+        _: &epoch::Guard,
+    ) -> Result<(), PrefixStoreError> {
+        self.path_selections
+            .lock()
+            .unwrap()
+            .push(Box::new(PathSelections { best, backup }));
+        self.ps_outdated.store(false, Ordering::Release);
+        Ok(())
+    }
+}
+
+pub struct CustomAllocStorage<AF, M, NB, PB> {
+    pub withdrawn_muis_bmin: epoch::Atomic<Bitmap>,
+    pub counters: Counters,
+    prefixes: Mutex<BTreeMap<PrefixId<AF>, Box<StoredPrefix<AF, M>>>>,
+    _m: PhantomData<(NB, PB)>,
+}
+impl<AF: AddressFamily, M: Meta, NB, PB> CustomAllocStorage<AF, M, NB, PB> {
+    fn new() -> Self {
+        CustomAllocStorage {
+            withdrawn_muis_bmin: epoch::Atomic(Bitmap::default()),
+            counters: Counters,
+            prefixes: Mutex::new(BTreeMap::new()),
+            _m: PhantomData,
+        }
+    }
+    // All the stored prefixes for which `filter` holds, in descending
+    // order.
+    fn stored_prefixes(&self, filter: impl Fn(&PrefixId<AF>) -> bool) -> Vec<&StoredPrefix<AF, M>> {
+        self.prefixes
+            .lock()
+            .unwrap()
+            .iter()
+            .rev()
+            .filter(|(id, _)| filter(id))
+            .map(|(_, p)| unsafe { extend(&**p, self) })
+            .collect()
+    }
+    fn get_or_insert(&self, id: PrefixId<AF>) -> (&StoredPrefix<AF, M>, bool) {
+        let mut prefixes = self.prefixes.lock().unwrap();
+        let mut new = false;
+        let p = prefixes.entry(id).or_insert_with(|| {
+            new = true;
+            Box::new(StoredPrefix::new(id))
+        });
+        (unsafe { extend(&**p, self) }, new)
+    }
+    // The records of `p` for `mui`, if set, with the global status of
+    // their mui applied.
+    fn records(
+        &self,
+        p: &StoredPrefix<AF, M>,
+        mui: Option<u32>,
+        include_withdrawn: bool,
+    ) -> Vec<Record<M>> {
+        p.record_map
+            .records()
+            .into_iter()
+            .filter(|r| mui.map_or(true, |mui| mui == r.multi_uniq_id))
+            .map(|mut r| {
+                if self.withdrawn_muis_bmin.0.contains(r.multi_uniq_id) {
+                    r.status = RouteStatus::Withdrawn;
+                }
+                r
+            })
+            .filter(|r| include_withdrawn || r.status != RouteStatus::Withdrawn)
+            .collect()
+    }
+    fn prefix_records(
+        &self,
+        filter: impl Fn(&PrefixId<AF>) -> bool,
+        mui: Option<u32>,
+        include_withdrawn: bool,
+    ) -> Vec<(Prefix, Vec<Record<M>>)> {
+        self.stored_prefixes(filter)
+            .into_iter()
+            .map(|p| (p.prefix.into_pub(), self.records(p, mui, include_withdrawn)))
+            .filter(|(_, records)| !records.is_empty())
+            .collect()
+    }
+    pub fn non_recursive_retrieve_prefix(
+        &self,
+        id: PrefixId<AF>,
+    ) -> (Option<&StoredPrefix<AF, M>>, Option<()>) {
+        let prefixes = self.prefixes.lock().unwrap();
+        (
+            prefixes.get(&id).map(|p| unsafe { extend(&**p, self) }),
+            None,
+        )
+    }
+    pub fn less_specific_prefix_iter<'a>(
+        &'a self,
+        id: PrefixId<AF>,
+        mui: Option<u32>,
+        include_withdrawn: bool,
+        _: &'a epoch::Guard,
+    ) -> impl Iterator<Item = (Prefix, Vec<Record<M>>)> + 'a {
+        self.prefix_records(
+            |p| p.covers(&id) && p.get_len() < id.get_len(),
+            mui,
+            include_withdrawn,
+        )
+        .into_iter()
+    }
+    // Like in rotonda-store, this does not return the prefix it starts
+    // from.
+    pub fn more_specific_prefix_iter_from<'a>(
+        &'a self,
+        id: PrefixId<AF>,
+        mui: Option<u32>,
+        include_withdrawn: bool,
+        _: &'a epoch::Guard,
+    ) -> impl Iterator<Item = (Prefix, Vec<Record<M>>)> + 'a {
+        self.prefix_records(
+            |p| id.covers(p) && p.get_len() > id.get_len(),
+            mui,
+            include_withdrawn,
+        )
+        .into_iter()
+    }
+    // Not in rotonda-store yet.
+    pub fn stored_prefixes_iter<'a>(
+        &'a self,
+        _: &'a epoch::Guard,
+    ) -> impl Iterator<Item = &'a StoredPrefix<AF, M>> + 'a {
+        self.stored_prefixes(|_| true).into_iter()
+    }
+    pub fn prefixes_iter(&self) -> impl Iterator<Item = (Prefix, Vec<Record<M>>)> + '_ {
+        self.stored_prefixes(|_| true)
+            .into_iter()
+            .map(|p| (p.prefix.into_pub(), p.record_map.records()))
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+    pub fn mark_mui_as_withdrawn_for_prefix(
+        &self,
+        id: PrefixId<AF>,
+        mui: u32,
+    ) -> Result<(), PrefixStoreError> {
+        self.set_status(id, mui, RouteStatus::Withdrawn)
+    }
+    pub fn mark_mui_as_active_for_prefix(
+        &self,
+        id: PrefixId<AF>,
+        mui: u32,
+    ) -> Result<(), PrefixStoreError> {
+        self.set_status(id, mui, RouteStatus::Active)
+    }
+    fn set_status(
+        &self,
+        id: PrefixId<AF>,
+        mui: u32,
+        status: RouteStatus,
+    ) -> Result<(), PrefixStoreError> {
+        let p = self
+            .non_recursive_retrieve_prefix(id)
+            .0
+            .ok_or(PrefixStoreError::PrefixNotFound)?;
+        p.record_map.set_status(mui, status)?;
+        p.ps_outdated.store(true, Ordering::Release);
+        Ok(())
+    }
+    pub fn mark_mui_as_withdrawn(
+        &self,
+        mui: u32,
+        _: &epoch::Guard,
+    ) -> Result<(), PrefixStoreError> {
+        self.withdrawn_muis_bmin.0 .0.lock().unwrap().insert(mui);
+        Ok(())
+    }
+    pub fn mark_mui_as_active(&self, mui: u32, _: &epoch::Guard) -> Result<(), PrefixStoreError> {
+        self.withdrawn_muis_bmin.0 .0.lock().unwrap().remove(&mui);
+        Ok(())
+    }
+    pub fn mui_is_withdrawn(&self, mui: u32, _: &epoch::Guard) -> bool {
+        self.withdrawn_muis_bmin.0.contains(mui)
+    }
+    pub fn get_prefixes_count(&self) -> usize {
+        self.prefixes.lock().unwrap().len()
+    }
+    pub fn get_prefixes_count_for_len(&self, len: u8) -> usize {
+        self.stored_prefixes(|p| p.get_len() == len).len()
+    }
+    pub fn get_nodes_count(&self) -> usize {
+        0
+    }
+}
+pub struct TreeBitMap<AF, M, NB, PB> {
+    pub store: CustomAllocStorage<AF, M, NB, PB>,
+}
+impl<AF: AddressFamily, M: Meta, NB: NodeBuckets<AF>, PB: PrefixBuckets<AF, M>>
+    TreeBitMap<AF, M, NB, PB>
+{
+    pub fn new() -> Result<Self, Box<dyn std::error::Error>> {
+        Ok(TreeBitMap {
+            store: CustomAllocStorage::new(),
+        })
+    }
+    pub fn match_prefix_by_store_direct(
+        &self,
+        id: PrefixId<AF>,
+        options: &MatchOptions,
+        mui: Option<u32>,
+        guard: &epoch::Guard,
+    ) -> QueryResult<M> {
+        let include_withdrawn = options.include_withdrawn;
+        let found = self
+            .store
+            .prefix_records(|p| p.covers(&id), mui, include_withdrawn)
+            .into_iter()
+            .max_by_key(|(prefix, _)| prefix.len());
+        let (match_type, prefix, prefix_meta) = match found {
+            Some((prefix, records)) if prefix.len() == id.get_len() => {
+                (MatchType::ExactMatch, Some(prefix), records)
+            }
+            Some((prefix, records)) if options.match_type != MatchType::ExactMatch => {
+                (MatchType::LongestMatch, Some(prefix), records)
+            }
+            _ => (MatchType::EmptyMatch, None, vec![]),
+        };
+        let search = prefix.map_or(id, PrefixId::from);
+        QueryResult {
+            match_type,
+            prefix,
+            prefix_meta,
+            less_specifics: options.include_less_specifics.then(|| {
+                self.less_specifics_from(search, mui, include_withdrawn, guard)
+                    .less_specifics
+                    .unwrap()
+            }),
+            more_specifics: options.include_more_specifics.then(|| {
+                self.more_specifics_from(search, mui, include_withdrawn, guard)
+                    .more_specifics
+                    .unwrap()
+            }),
+        }
+    }
+    pub fn more_specifics_from(
+        &self,
+        id: PrefixId<AF>,
+        mui: Option<u32>,
+        include_withdrawn: bool,
+        guard: &epoch::Guard,
+    ) -> QueryResult<M> {
+        let more_specifics = self
+            .store
+            .more_specific_prefix_iter_from(id, mui, include_withdrawn, guard)
+            .map(PrefixRecord::from)
+            .collect();
+        QueryResult {
+            match_type: MatchType::EmptyMatch,
+            prefix: None,
+            prefix_meta: vec![],
+            less_specifics: None,
+            more_specifics: Some(RecordSet(more_specifics)),
+        }
+    }
+    pub fn less_specifics_from(
+        &self,
+        id: PrefixId<AF>,
+        mui: Option<u32>,
+        include_withdrawn: bool,
+        guard: &epoch::Guard,
+    ) -> QueryResult<M> {
+        let less_specifics = self
+            .store
+            .less_specific_prefix_iter(id, mui, include_withdrawn, guard)
+            .map(PrefixRecord::from)
+            .collect();
+        QueryResult {
+            match_type: MatchType::EmptyMatch,
+            prefix: None,
+            prefix_meta: vec![],
+            less_specifics: Some(RecordSet(less_specifics)),
+            more_specifics: None,
+        }
+    }
+    pub fn insert(
+        &self,
+        id: PrefixId<AF>,
+        record: Record<M>,
+        update_path_selections: Option<M::TBI>,
+    ) -> Result<UpsertReport, PrefixStoreError> {
+        let (p, prefix_new) = self.store.get_or_insert(id);
+        let mui_new = p.record_map.upsert(record);
+        p.ps_outdated.store(true, Ordering::Release);
+        if let Some(tbi) = update_path_selections {
+            p.calculate_and_store_best_backup(&tbi, &epoch::pin())?;
+        }
+        Ok(UpsertReport {
+            cas_count: 0,
+            prefix_new,
+            mui_new,
+            mui_count: p.record_map.len(),
+        })
+    }
+}
+impl<AF, M, NB, PB> std::fmt::Display for TreeBitMap<AF, M, NB, PB> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        Ok(())
+    }
+}
+impl<AF, M, NB, PB> std::fmt::Debug for TreeBitMap<AF, M, NB, PB> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("TreeBitMap").finish_non_exhaustive()
+    }
+}
+
+// Not in rotonda-store yet.
+pub trait SnapshotMeta: Sized {
+    fn write_snapshot<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()>;
+    fn read_snapshot<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self>;
+}
+
+// Not in rotonda-store yet.
+pub trait MrtMeta {
+    fn mrt_path_attributes(&self) -> Vec<u8>;
+    fn mrt_peer_bgp_id(&self) -> std::net::Ipv4Addr;
+    fn mrt_peer_addr(&self) -> std::net::IpAddr;
+    fn mrt_peer_asn(&self) -> u32;
+}
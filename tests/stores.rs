@@ -0,0 +1,871 @@
+// Tests the code generated by the macros against the stand-in of
+// rotonda-store, for the different ways the macros can be invoked.
+//
+// The stand-in is a simple in-memory store, so this tests the generated
+// methods, not rotonda-store.
+
+// The generated code has items behind the `cli` feature of rotonda-store.
+#![allow(unexpected_cfgs)]
+
+mod stand_in;
+
+use std::net::{IpAddr, Ipv4Addr};
+
+use stand_in::prelude::multi::{create_store, epoch, strides, Meta, PrefixStoreError};
+use stand_in::prelude::{MatchOptions, MatchType, Prefix, Record, RouteStatus};
+use stand_in::{MrtMeta, SnapshotMeta};
+
+mod default_strides {
+    use super::*;
+
+    #[create_store(crate = "crate::stand_in")]
+    pub struct Store;
+}
+
+mod named_args {
+    use super::*;
+
+    #[create_store(v4 = [4; 8], v6 = [5, 5, 3, 3], crate = "crate::stand_in")]
+    pub struct Store;
+}
+
+mod tuple_args {
+    use super::*;
+
+    #[create_store(([5, 5, 4, 3, 3, 3, 3, 3, 3], [4; 32]), crate = "crate::stand_in")]
+    pub struct Store;
+}
+
+mod v4_only {
+    use super::*;
+
+    #[create_store(v6 = None, crate = "crate::stand_in")]
+    pub struct Store;
+}
+
+mod v6_only {
+    use super::*;
+
+    #[create_store(crate = "crate::stand_in", v4 = None)]
+    pub struct Store;
+}
+
+mod strides_consts {
+    use super::*;
+
+    strides! {
+        const V4 = [4; 8];
+        const V6 = [5, 5, 3, 3];
+    }
+
+    #[create_store(v4 = V4, v6 = V6, crate = "crate::stand_in")]
+    pub struct Store;
+}
+
+mod layouts {
+    use super::*;
+
+    strides! {
+        pub const V4 = [5, 5, 4, 3, 3, 3, 3, 3, 3];
+        pub(crate) const V6 = [4; 32];
+    }
+}
+
+// The consts defined with `strides!` can be used by path from another
+// module.
+mod strides_paths {
+    use super::*;
+
+    #[create_store(v4 = super::layouts::V4, v6 = crate::layouts::V6, crate = "crate::stand_in")]
+    pub struct Store;
+}
+
+#[cfg(feature = "extra-strides")]
+mod extra_strides {
+    use super::*;
+
+    #[create_store(v4 = [1, 2, 6, 7, 8, 8], v6 = [8; 16], crate = "crate::stand_in")]
+    pub struct Store;
+}
+
+mod bucket_maps {
+    use super::*;
+
+    #[create_store(bucket_map = (legacy, uniform(8)), crate = "crate::stand_in")]
+    pub struct Store;
+}
+
+mod bucket_map_table {
+    use super::*;
+
+    strides! {
+        const V4 = [4; 8];
+    }
+
+    #[create_store(
+        v4 = V4,
+        v6 = None,
+        bucket_map = [
+            [0], [1], [2], [3], [4], [5], [6], [7], [8], [9], [10], [11], [12],
+            [12, 13], [12, 14], [12, 15], [12, 16], [12, 17], [12, 18],
+            [12, 19], [12, 20], [12, 21], [12, 22], [12, 23], [12, 24],
+            [12, 24, 25], [12, 24, 26], [12, 24, 27], [12, 24, 28],
+            [12, 24, 29], [12, 24, 30], [12, 24, 31], [12, 24, 32],
+        ],
+        crate = "crate::stand_in"
+    )]
+    pub struct Table;
+}
+
+// The generated code does not clash with the names in the preludes.
+#[allow(unused_imports)]
+mod glob_imports {
+    use crate::stand_in::prelude::multi::*;
+    use crate::stand_in::prelude::*;
+
+    #[create_store(crate = "crate::stand_in")]
+    pub struct Store;
+}
+
+mod attributes {
+    use super::*;
+
+    /// A store that is never compiled.
+    #[create_store(crate = "crate::stand_in")]
+    #[cfg(any())]
+    pub struct Gated;
+
+    /// A store that is only visible in this crate.
+    #[create_store(crate = "crate::stand_in")]
+    #[allow(missing_docs)]
+    #[must_use]
+    pub(crate) struct Store;
+}
+
+mod debug_store {
+    use super::*;
+
+    #[create_store(crate = "crate::stand_in")]
+    #[derive(Debug)]
+    pub struct Store;
+}
+
+// A meta-data type that can be exported to MRT and written to snapshots.
+#[derive(Clone, Debug, PartialEq, Meta)]
+#[meta(crate = "crate::stand_in")]
+struct Exported {
+    #[meta(local_pref)]
+    local_pref: u32,
+    peer_asn: u32,
+}
+
+impl MrtMeta for Exported {
+    fn mrt_path_attributes(&self) -> Vec<u8> {
+        self.local_pref.to_be_bytes().to_vec()
+    }
+
+    fn mrt_peer_bgp_id(&self) -> Ipv4Addr {
+        Ipv4Addr::from(self.peer_asn)
+    }
+
+    fn mrt_peer_addr(&self) -> IpAddr {
+        Ipv4Addr::from(self.peer_asn).into()
+    }
+
+    fn mrt_peer_asn(&self) -> u32 {
+        self.peer_asn
+    }
+}
+
+impl SnapshotMeta for Exported {
+    fn write_snapshot<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        writer.write_all(&self.local_pref.to_be_bytes())?;
+        writer.write_all(&self.peer_asn.to_be_bytes())
+    }
+
+    fn read_snapshot<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let mut buf = [0; 8];
+        reader.read_exact(&mut buf)?;
+        Ok(Exported {
+            local_pref: u32::from_be_bytes(buf[..4].try_into().unwrap()),
+            peer_asn: u32::from_be_bytes(buf[4..].try_into().unwrap()),
+        })
+    }
+}
+
+fn pfx(s: &str) -> Prefix {
+    let (addr, len) = s.split_once('/').unwrap();
+    Prefix::new(addr.parse().unwrap(), len.parse().unwrap()).unwrap()
+}
+
+// An active record for the peer with `mui`, that has AS number 65000 +
+// `mui`.
+fn record(mui: u32, ltime: u64, local_pref: u32) -> Record<Exported> {
+    Record::new(
+        mui,
+        ltime,
+        RouteStatus::Active,
+        Exported {
+            local_pref,
+            peer_asn: 65000 + mui,
+        },
+    )
+}
+
+// Every store can be built, and stores the prefixes of the address families
+// it has a tree for.
+#[test]
+fn build_stores() {
+    macro_rules! check {
+        ($store:ty, $v4:expr, $v6:expr) => {{
+            let store = <$store>::new().unwrap();
+            for (prefix, supported) in [(pfx("10.0.0.0/8"), $v4), (pfx("2001:db8::/32"), $v6)] {
+                let res = store.insert(&prefix, record(1, 0, 100), None);
+                if supported {
+                    assert!(res.unwrap().prefix_new);
+                } else {
+                    assert_eq!(res.unwrap_err(), PrefixStoreError::UnsupportedAddressFamily);
+                }
+            }
+            assert_eq!(store.prefixes_count(), $v4 as usize + $v6 as usize);
+        }};
+    }
+
+    check!(default_strides::Store<Exported>, true, true);
+    check!(named_args::Store<Exported>, true, true);
+    check!(tuple_args::Store<Exported>, true, true);
+    check!(v4_only::Store<Exported>, true, false);
+    check!(v6_only::Store<Exported>, false, true);
+    check!(strides_consts::Store<Exported>, true, true);
+    check!(strides_paths::Store<Exported>, true, true);
+    #[cfg(feature = "extra-strides")]
+    check!(extra_strides::Store<Exported>, true, true);
+    check!(bucket_maps::Store<Exported>, true, true);
+    check!(bucket_map_table::Table<Exported>, true, false);
+    check!(glob_imports::Store<Exported>, true, true);
+    check!(attributes::Store<Exported>, true, true);
+    check!(debug_store::Store<Exported>, true, true);
+}
+
+// The store with the default strides stores prefixes of any length.
+#[test]
+fn default_strides() {
+    let store = default_strides::Store::<Exported>::new().unwrap();
+    for p in [
+        "0.0.0.0/0",
+        "10.0.0.0/8",
+        "192.0.2.1/32",
+        "::/0",
+        "2001:db8::1/128",
+    ] {
+        store.insert(&pfx(p), record(1, 0, 100), None).unwrap();
+    }
+    assert_eq!(store.prefixes_v4_count(), 3);
+    assert_eq!(store.prefixes_v4_count_for_len(32), 1);
+    assert_eq!(store.prefixes_v6_count_for_len(128), 1);
+}
+
+// Withdrawing a mui for IPv4 only leaves its IPv6 records active.
+#[test]
+fn withdraw_mui_per_family() {
+    let store = attributes::Store::<Exported>::new().unwrap();
+    let guard = &epoch::pin();
+    let (pfx4, pfx6) = (pfx("10.0.0.0/8"), pfx("2001:db8::/32"));
+    for p in [&pfx4, &pfx6] {
+        store.insert(p, record(1, 0, 100), None).unwrap();
+    }
+    store.mark_mui_as_withdrawn_v4(1).unwrap();
+
+    let status = |prefix| {
+        let options = MatchOptions {
+            match_type: MatchType::ExactMatch,
+            include_withdrawn: true,
+            include_less_specifics: false,
+            include_more_specifics: false,
+            mui: None,
+        };
+        let res = store.match_prefix(prefix, &options, guard);
+        res.prefix_meta.iter().map(|r| r.status).collect::<Vec<_>>()
+    };
+
+    assert_eq!(status(&pfx4), [RouteStatus::Withdrawn]);
+    assert_eq!(status(&pfx6), [RouteStatus::Active]);
+    assert!(store.mui_is_withdrawn_v4(1));
+    assert!(!store.mui_is_withdrawn_v6(1));
+    let mui_status = store.mui_status(1);
+    assert_eq!(mui_status.v4, Some(RouteStatus::Withdrawn));
+    assert_eq!(mui_status.v6, Some(RouteStatus::Active));
+    assert_eq!(
+        store
+            .iter_records_for_mui(1, false, guard)
+            .map(|p| p.prefix)
+            .collect::<Vec<_>>(),
+        [pfx6]
+    );
+}
+
+// The result of activating a mui is reported for each address family.
+#[test]
+fn mui_report() {
+    let store = v4_only::Store::<Exported>::new().unwrap();
+    store.mark_mui_as_withdrawn(1).unwrap();
+    assert_eq!(store.mui_status(1).v4, Some(RouteStatus::Withdrawn));
+
+    let report = store.mark_mui_as_active(1);
+    assert_eq!(report.v4, Some(Ok(())));
+    assert_eq!(report.v6, None);
+    assert_eq!(store.mui_status(1).v4, Some(RouteStatus::Active));
+}
+
+// The ordered iterators return the prefixes in address-then-length order,
+// also for parts of the tree that are too large to be taken in one go.
+#[test]
+fn ordered_prefixes() {
+    let store = attributes::Store::<Exported>::new().unwrap();
+    let mut prefixes = vec![pfx("0.0.0.0/0"), pfx("::/0"), pfx("2001:db8::/32")];
+    for idx in 0_u32..300 {
+        // Spread the prefixes over the address space, in no particular
+        // order.
+        let addr = idx.wrapping_mul(0x9e37_79b9) & 0xffff_ff00;
+        let len = 8 + (idx % 17) as u8;
+        let addr = addr & (u32::MAX << (32 - len));
+        prefixes.push(Prefix::new(Ipv4Addr::from(addr).into(), len).unwrap());
+        prefixes.push(Prefix::new(Ipv4Addr::from(addr).into(), 24).unwrap());
+    }
+    for p in &prefixes {
+        store.insert(p, record(1, 0, 100), None).unwrap();
+    }
+    let key = |p: &Prefix| (p.addr(), p.len());
+    prefixes.sort_by_key(key);
+    prefixes.dedup();
+
+    let ordered = store
+        .prefixes_iter_ordered()
+        .map(|p| p.prefix)
+        .collect::<Vec<_>>();
+    assert_eq!(ordered, prefixes);
+
+    // Paging through the store returns every prefix once.
+    let mut paged = vec![];
+    let mut start = pfx("0.0.0.0/0");
+    let end = pfx("ffff:ffff:ffff:ffff:ffff:ffff:ffff:ffff/128");
+    loop {
+        let page = store
+            .prefixes_in_range(&start, &end)
+            .take(50)
+            .map(|p| p.prefix)
+            .collect::<Vec<_>>();
+        paged.extend_from_slice(&page[..page.len().min(49)]);
+        match page.get(49) {
+            Some(next) => start = *next,
+            None => break,
+        }
+    }
+    assert_eq!(paged, prefixes);
+
+    // A range within one address family.
+    let (start, end) = (prefixes[100], prefixes[200]);
+    assert_eq!(
+        store
+            .prefixes_in_range(&start, &end)
+            .map(|p| p.prefix)
+            .collect::<Vec<_>>(),
+        prefixes[100..200]
+    );
+}
+
+// The recalculation of the best paths includes the default routes.
+#[test]
+fn recalculate_default_route() {
+    let store = attributes::Store::<Exported>::new().unwrap();
+    let guard = &epoch::pin();
+    let best = |prefix: &str| {
+        store
+            .best_path(&pfx(prefix), guard)
+            .unwrap()
+            .map(|r| r.multi_uniq_id)
+    };
+    for p in ["0.0.0.0/0", "10.0.0.0/8", "::/0"] {
+        store.insert(&pfx(p), record(1, 0, 100), None).unwrap();
+        store.insert(&pfx(p), record(2, 0, 200), None).unwrap();
+    }
+
+    let report = store.recalculate_best_paths_for_mui(1, &(), guard).unwrap();
+    assert_eq!(report.prefixes_checked, 3);
+    assert_eq!(report.prefixes_recalculated, 3);
+    assert_eq!(best("0.0.0.0/0"), Ok(2));
+    assert_eq!(best("::/0"), Ok(2));
+
+    store
+        .insert(&pfx("0.0.0.0/0"), record(1, 1, 300), None)
+        .unwrap();
+    store.insert(&pfx("::/0"), record(1, 1, 300), None).unwrap();
+    let report = store.recalculate_all_outdated(&(), guard).unwrap();
+    assert_eq!(report.prefixes_checked, 3);
+    assert_eq!(report.prefixes_recalculated, 2);
+    assert_eq!(report.best_changed, 2);
+    assert_eq!(best("0.0.0.0/0"), Ok(1));
+    assert_eq!(best("::/0"), Ok(1));
+}
+
+// After a session flap, the withdrawn mui is no longer the best path, and
+// it is again after it comes back.
+#[test]
+fn recalculate_session_flap() {
+    let store = attributes::Store::<Exported>::new().unwrap();
+    let guard = &epoch::pin();
+    let best = || {
+        store
+            .best_path(&pfx("10.0.0.0/8"), guard)
+            .unwrap()
+            .map(|r| r.multi_uniq_id)
+    };
+    store
+        .insert(&pfx("10.0.0.0/8"), record(1, 0, 100), Some(()))
+        .unwrap();
+    store
+        .insert(&pfx("10.0.0.0/8"), record(2, 0, 200), Some(()))
+        .unwrap();
+    assert_eq!(best(), Ok(2));
+
+    store.mark_mui_as_withdrawn(2).unwrap();
+    let report = store.recalculate_best_paths_for_mui(2, &(), guard).unwrap();
+    assert_eq!(report.prefixes_checked, 1);
+    assert_eq!(report.prefixes_recalculated, 1);
+    assert_eq!(report.best_changed, 1);
+    assert_eq!(best(), Ok(1));
+
+    assert_eq!(store.mark_mui_as_active(2).v4, Some(Ok(())));
+    let report = store.recalculate_best_paths_for_mui(2, &(), guard).unwrap();
+    assert_eq!(report.best_changed, 1);
+    assert_eq!(best(), Ok(2));
+}
+
+#[cfg(feature = "mrt")]
+#[test]
+fn export_mrt() {
+    let store = attributes::Store::<Exported>::new().unwrap();
+    store
+        .insert(&pfx("10.0.0.0/8"), record(1, 0, 100), None)
+        .unwrap();
+    store
+        .insert(&pfx("10.0.0.0/8"), record(2, 0, 200), None)
+        .unwrap();
+    store
+        .insert(&pfx("10.1.0.0/16"), record(2, 0, 200), None)
+        .unwrap();
+    store
+        .insert(&pfx("2001:db8::/32"), record(3, 0, 300), None)
+        .unwrap();
+    store
+        .mark_mui_as_withdrawn_for_prefix(&pfx("10.1.0.0/16"), 2)
+        .unwrap();
+
+    let mut buf = vec![];
+    store
+        .export_mrt_table_dump_v2(&mut buf, Ipv4Addr::LOCALHOST, "rib", 7)
+        .unwrap();
+
+    // The (subtype, body) of the MRT records.
+    let mut records = vec![];
+    let mut rest = &buf[..];
+    while !rest.is_empty() {
+        let (header, tail) = rest.split_at(12);
+        assert_eq!(header[..4], 7_u32.to_be_bytes());
+        assert_eq!(header[4..6], 13_u16.to_be_bytes());
+        let subtype = u16::from_be_bytes([header[6], header[7]]);
+        let len = u32::from_be_bytes(header[8..].try_into().unwrap()) as usize;
+        records.push((subtype, &tail[..len]));
+        rest = &tail[len..];
+    }
+
+    // The peer index table, followed by the prefixes that have an exported
+    // record, without the withdrawn 10.1.0.0/16.
+    assert_eq!(
+        records
+            .iter()
+            .map(|(subtype, _)| *subtype)
+            .collect::<Vec<_>>(),
+        [1, 2, 4]
+    );
+    let peer_index = records[0].1;
+    assert_eq!(peer_index[..4], [127, 0, 0, 1]);
+    assert_eq!(peer_index[4..9], *b"\0\x03rib");
+    assert_eq!(peer_index[9..11], 3_u16.to_be_bytes());
+
+    // Sequence number, prefix length, prefix and the RIB entries for peer
+    // 0 and 1.
+    let rib = records[1].1;
+    assert_eq!(rib[..8], [0, 0, 0, 0, 8, 10, 0, 2]);
+    let entries = rib[8..].chunks(12).collect::<Vec<_>>();
+    assert_eq!(entries.len(), 2);
+    for (idx, entry) in entries.into_iter().enumerate() {
+        assert_eq!(entry[..2], (idx as u16).to_be_bytes());
+        assert_eq!(entry[8..], (100 * (idx as u32 + 1)).to_be_bytes());
+    }
+}
+
+#[test]
+fn observe() {
+    let store = attributes::Store::<Exported>::new().unwrap();
+    let (tx, rx) = std::sync::mpsc::channel();
+    store.set_best_path_observer(move |prefix, _, new_best, _, new_backup| {
+        let _ = tx.send((prefix, new_best, new_backup));
+    });
+
+    let prefix = pfx("10.0.0.0/8");
+    store.insert(&prefix, record(1, 0, 100), Some(())).unwrap();
+    store.insert(&prefix, record(2, 0, 200), Some(())).unwrap();
+    // Without the path selection, and without a change, nothing is seen.
+    store.insert(&prefix, record(3, 0, 50), None).unwrap();
+    store.insert(&prefix, record(2, 1, 200), Some(())).unwrap();
+
+    assert_eq!(
+        rx.try_iter().collect::<Vec<_>>(),
+        [(prefix, Some(1), None), (prefix, Some(2), Some(1))]
+    );
+}
+
+// The observer can be set on a store that is shared between threads, and
+// the store can derive Debug.
+#[test]
+fn observe_shared() {
+    let store = std::sync::Arc::new(debug_store::Store::<Exported>::new().unwrap());
+    assert!(format!("{:?}", store).contains("best_path_observer: None"));
+
+    let (tx, rx) = std::sync::mpsc::channel();
+    store.set_best_path_observer(move |prefix, _, new_best, _, _| {
+        let _ = tx.send((prefix, new_best));
+    });
+    assert!(format!("{:?}", store).contains("best_path_observer: Some(..)"));
+
+    let shared = store.clone();
+    std::thread::spawn(move || {
+        shared
+            .insert(&pfx("10.0.0.0/8"), record(1, 0, 100), Some(()))
+            .unwrap();
+    })
+    .join()
+    .unwrap();
+    assert_eq!(
+        rx.try_iter().collect::<Vec<_>>(),
+        [(pfx("10.0.0.0/8"), Some(1))]
+    );
+
+    store.clear_best_path_observer();
+    store
+        .insert(&pfx("10.0.0.0/8"), record(2, 0, 200), Some(()))
+        .unwrap();
+    assert_eq!(rx.try_iter().count(), 0);
+}
+
+#[test]
+fn adj_rib_in() {
+    let store = attributes::Store::<Exported>::new().unwrap();
+    let guard = &epoch::pin();
+    for p in [
+        "0.0.0.0/0",
+        "10.0.0.0/8",
+        "10.1.0.0/16",
+        "192.0.2.0/24",
+        "2001:db8::/32",
+    ] {
+        store.insert(&pfx(p), record(1, 0, 100), None).unwrap();
+    }
+    store
+        .insert(&pfx("10.2.0.0/16"), record(2, 0, 100), None)
+        .unwrap();
+    store.insert(&pfx("::/0"), record(2, 0, 100), None).unwrap();
+
+    let prefixes = |within: Option<&str>| {
+        let mut prefixes = store
+            .iter_records_for_mui_within(1, within.map(pfx).as_ref(), false, guard)
+            .map(|p| p.prefix)
+            .collect::<Vec<_>>();
+        prefixes.sort();
+        prefixes
+    };
+
+    assert_eq!(
+        prefixes(Some("10.0.0.0/8")),
+        [pfx("10.0.0.0/8"), pfx("10.1.0.0/16")]
+    );
+    assert_eq!(prefixes(Some("2001:db8::/16")), [pfx("2001:db8::/32")]);
+
+    // Without a covering prefix the default routes are included.
+    assert_eq!(
+        prefixes(None),
+        [
+            pfx("0.0.0.0/0"),
+            pfx("10.0.0.0/8"),
+            pfx("10.1.0.0/16"),
+            pfx("192.0.2.0/24"),
+            pfx("2001:db8::/32")
+        ]
+    );
+    assert_eq!(store.iter_records_for_mui(2, false, guard).count(), 2);
+
+    // The records of the covering prefix are withdrawn with the mui, like
+    // those of the more-specifics.
+    store.mark_mui_as_withdrawn_v4(1).unwrap();
+    let statuses = store
+        .iter_records_for_mui_within(1, Some(&pfx("10.0.0.0/8")), true, guard)
+        .flat_map(|p| p.meta.into_iter().map(|r| r.status))
+        .collect::<Vec<_>>();
+    assert_eq!(statuses, [RouteStatus::Withdrawn, RouteStatus::Withdrawn]);
+}
+
+#[test]
+fn poll() {
+    let store = attributes::Store::<Exported>::new().unwrap();
+    let guard = &epoch::pin();
+    store
+        .insert(&pfx("10.0.0.0/8"), record(1, 1, 100), None)
+        .unwrap();
+    store
+        .insert(&pfx("10.0.0.0/8"), record(2, 2, 100), None)
+        .unwrap();
+    store
+        .insert(&pfx("2001:db8::/32"), record(1, 3, 100), None)
+        .unwrap();
+
+    let changes = store
+        .changes_since(1, guard)
+        .map(|p| (p.prefix, p.meta.iter().map(|r| r.ltime).collect::<Vec<_>>()))
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        changes,
+        [
+            (pfx("10.0.0.0/8"), vec![2]),
+            (pfx("2001:db8::/32"), vec![3])
+        ]
+    );
+    assert_eq!(store.changes_since(3, guard).count(), 0);
+
+    // An update of a default route is returned as well.
+    store
+        .insert(&pfx("0.0.0.0/0"), record(1, 4, 100), None)
+        .unwrap();
+    store.insert(&pfx("::/0"), record(1, 5, 100), None).unwrap();
+    assert_eq!(
+        store
+            .changes_since(3, guard)
+            .map(|p| p.prefix)
+            .collect::<Vec<_>>(),
+        [pfx("0.0.0.0/0"), pfx("::/0")]
+    );
+
+    // A change of the global status of a mui returns all its records, with
+    // the status as seen by the store and a logical time above the highest
+    // one in the store. The prefixes are returned in no particular order.
+    let changes = |ltime| {
+        let mut changes = store
+            .changes_since(ltime, guard)
+            .flat_map(|p| {
+                p.meta
+                    .into_iter()
+                    .map(move |r| (p.prefix, r.multi_uniq_id, r.ltime, r.status))
+            })
+            .collect::<Vec<_>>();
+        changes.sort_by_key(|(prefix, mui, _, _)| (*prefix, *mui));
+        changes
+    };
+    store.mark_mui_as_withdrawn(1).unwrap();
+    assert_eq!(
+        changes(5),
+        [
+            (pfx("0.0.0.0/0"), 1, 6, RouteStatus::Withdrawn),
+            (pfx("10.0.0.0/8"), 1, 6, RouteStatus::Withdrawn),
+            (pfx("::/0"), 1, 7, RouteStatus::Withdrawn),
+            (pfx("2001:db8::/32"), 1, 7, RouteStatus::Withdrawn),
+        ]
+    );
+    assert_eq!(changes(7), []);
+
+    // A change of the local status returns the record.
+    store
+        .mark_mui_as_withdrawn_for_prefix(&pfx("10.0.0.0/8"), 2)
+        .unwrap();
+    assert_eq!(
+        changes(7),
+        [(pfx("10.0.0.0/8"), 2, 8, RouteStatus::Withdrawn)]
+    );
+
+    // Activating the mui for IPv4 only returns its IPv4 records, with their
+    // local status.
+    store.mark_mui_as_active_v4(1).unwrap();
+    assert_eq!(
+        changes(8),
+        [
+            (pfx("0.0.0.0/0"), 1, 9, RouteStatus::Active),
+            (pfx("10.0.0.0/8"), 1, 9, RouteStatus::Active),
+        ]
+    );
+
+    // An insert after the status changes needs a higher logical time.
+    store
+        .insert(&pfx("10.0.0.0/8"), record(3, 10, 100), None)
+        .unwrap();
+    assert_eq!(
+        changes(9),
+        [(pfx("10.0.0.0/8"), 3, 10, RouteStatus::Active)]
+    );
+}
+
+#[test]
+fn fast_reroute() {
+    let store = attributes::Store::<Exported>::new().unwrap();
+    let guard = &epoch::pin();
+    let prefix = pfx("10.0.0.0/8");
+    let paths = || {
+        let (best, backup) = store.best_and_backup_paths(&prefix, guard)?.ok()?;
+        Some((best.multi_uniq_id, backup.map(|r| r.multi_uniq_id)))
+    };
+
+    assert_eq!(paths(), None);
+    store.insert(&prefix, record(1, 0, 100), Some(())).unwrap();
+    assert_eq!(paths(), Some((1, None)));
+    assert_eq!(
+        store.backup_path(&prefix, guard).unwrap().unwrap_err(),
+        PrefixStoreError::BestPathNotFound
+    );
+    store.insert(&prefix, record(2, 0, 200), Some(())).unwrap();
+    assert_eq!(paths(), Some((2, Some(1))));
+    assert_eq!(
+        store
+            .backup_path(&prefix, guard)
+            .unwrap()
+            .unwrap()
+            .multi_uniq_id,
+        1
+    );
+}
+
+#[cfg(feature = "snapshot")]
+#[test]
+fn snapshot() {
+    let store = attributes::Store::<Exported>::new().unwrap();
+    let guard = &epoch::pin();
+    store
+        .insert(&pfx("10.0.0.0/8"), record(1, 0, 100), Some(()))
+        .unwrap();
+    store
+        .insert(&pfx("10.0.0.0/8"), record(2, 0, 200), Some(()))
+        .unwrap();
+    store
+        .insert(&pfx("2001:db8::/32"), record(1, 0, 100), None)
+        .unwrap();
+
+    let mut buf = vec![];
+    store.write_snapshot(&mut buf).unwrap();
+    let read = attributes::Store::<Exported>::read_snapshot(&mut &buf[..]).unwrap();
+
+    assert_eq!(read.prefixes_count(), 2);
+    let best = read.best_path(&pfx("10.0.0.0/8"), guard).unwrap().unwrap();
+    assert_eq!(best.multi_uniq_id, 2);
+    assert_eq!(best.meta, record(2, 0, 200).meta);
+}
+
+mod derive_meta {
+    use super::*;
+    use crate::stand_in::Meta as _;
+
+    // The unmarked fields are only there to show that they are ignored.
+    #[allow(dead_code)]
+    #[derive(Clone, Debug, Default, Meta)]
+    #[meta(tbi = u32, crate = "crate::stand_in")]
+    struct Route {
+        #[meta(med)]
+        med: u32,
+        #[meta(local_pref)]
+        local_pref: u32,
+        #[meta(as_path_len)]
+        as_path_len: usize,
+        #[meta(origin)]
+        origin: u8,
+        #[meta(neighbor_as)]
+        neighbor_as: u32,
+        #[meta(bgp_id)]
+        bgp_id: u32,
+        #[meta(peer_addr)]
+        peer_addr: u32,
+        next_hop: Option<std::net::IpAddr>,
+    }
+
+    fn route(local_pref: u32, as_path_len: usize, origin: u8, med: u32) -> Route {
+        Route {
+            med,
+            local_pref,
+            as_path_len,
+            origin,
+            neighbor_as: 65001,
+            ..Default::default()
+        }
+    }
+
+    // The local AS, the TBI.
+    const LOCAL_AS: u32 = 65000;
+
+    #[allow(dead_code)]
+    #[derive(Clone, Debug, Meta)]
+    #[meta(crate = "crate::stand_in")]
+    struct Generic<T: Clone + Ord>(#[meta(local_pref)] T, u8);
+
+    #[test]
+    fn decision_process_steps() {
+        let better = |a: &Route, b: &Route| a.as_orderable(LOCAL_AS) > b.as_orderable(LOCAL_AS);
+
+        // A higher local preference wins over everything else.
+        assert!(better(&route(200, 5, 2, 9), &route(100, 1, 0, 0)));
+        // Then a shorter AS path.
+        assert!(better(&route(100, 1, 2, 9), &route(100, 2, 0, 0)));
+        // Then a lower origin.
+        assert!(better(&route(100, 1, 0, 9), &route(100, 1, 1, 0)));
+        // Then eBGP over iBGP.
+        let ibgp = Route {
+            neighbor_as: LOCAL_AS,
+            ..route(100, 1, 0, 0)
+        };
+        assert!(better(&route(100, 1, 0, 9), &ibgp));
+        // Then a lower MED.
+        assert!(better(&route(100, 1, 0, 0), &route(100, 1, 0, 1)));
+        // Then a lower BGP identifier.
+        let bgp_id = |bgp_id| Route {
+            bgp_id,
+            ..route(100, 1, 0, 0)
+        };
+        assert!(better(&bgp_id(1), &bgp_id(2)));
+        // Then a lower peer address.
+        let peer_addr = |peer_addr| Route {
+            peer_addr,
+            bgp_id: 1,
+            ..route(100, 1, 0, 0)
+        };
+        assert!(better(&peer_addr(1), &peer_addr(2)));
+    }
+
+    // The MED is only compared between paths from the same neighbor AS.
+    #[test]
+    fn med_per_neighbor_as() {
+        let path = |neighbor_as, med, bgp_id| Route {
+            neighbor_as,
+            bgp_id,
+            ..route(100, 1, 0, med)
+        };
+        let mut paths = [path(65002, 0, 1), path(65001, 20, 3), path(65001, 10, 2)];
+        paths.sort_by(|a, b| b.as_orderable(LOCAL_AS).cmp(&a.as_orderable(LOCAL_AS)));
+        assert_eq!(
+            paths
+                .iter()
+                .map(|p| (p.neighbor_as, p.med))
+                .collect::<Vec<_>>(),
+            [(65001, 10), (65001, 20), (65002, 0)]
+        );
+    }
+
+    #[test]
+    fn tuple_struct() {
+        assert!(Generic(2, 0).as_orderable(()) > Generic(1, 9).as_orderable(()));
+    }
+}
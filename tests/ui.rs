@@ -0,0 +1,16 @@
+// Checks the compile errors that the macros report for invalid input.
+//
+// Each file in tests/ui fails to compile with the errors in the `.stderr`
+// file next to it. Run with `TRYBUILD=overwrite` to update these after a
+// deliberate change of the errors. The errors for the stride sizes that
+// depend on the `extra-strides` feature are in the subdirectories.
+
+#[test]
+fn ui() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui/*.rs");
+    #[cfg(feature = "extra-strides")]
+    t.compile_fail("tests/ui/extra_strides/*.rs");
+    #[cfg(not(feature = "extra-strides"))]
+    t.compile_fail("tests/ui/no_extra_strides/*.rs");
+}
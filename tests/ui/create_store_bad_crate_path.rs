@@ -0,0 +1,6 @@
+use rotonda_macros::create_store;
+
+#[create_store(crate = "rotonda store")]
+struct Store;
+
+fn main() {}
@@ -0,0 +1,12 @@
+// The generated code has items behind the `cli` feature of rotonda-store.
+#![allow(unexpected_cfgs)]
+
+#[path = "../stand_in/mod.rs"]
+mod stand_in;
+
+use rotonda_macros::create_store;
+
+#[create_store(v4 = [4, 4, 4, 4, 0, 4, 4, 4, 4], crate = "crate::stand_in")]
+struct Store;
+
+fn main() {}
@@ -0,0 +1,6 @@
+use rotonda_macros::create_store;
+
+#[create_store(bucket_map = (legacy, legacy, legacy))]
+struct Store;
+
+fn main() {}
@@ -0,0 +1,6 @@
+use rotonda_macros::create_store;
+
+#[create_store(v4 = [4; 8], v4 = [8; 4])]
+struct Store;
+
+fn main() {}
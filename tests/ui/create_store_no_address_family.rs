@@ -0,0 +1,6 @@
+use rotonda_macros::create_store;
+
+#[create_store(v4 = None, v6 = None)]
+struct Store;
+
+fn main() {}
@@ -0,0 +1,16 @@
+// The generated code has items behind the `cli` feature of rotonda-store.
+#![allow(unexpected_cfgs)]
+
+#[path = "../stand_in/mod.rs"]
+mod stand_in;
+
+use rotonda_macros::create_store;
+
+fn strides() -> [u8; 8] {
+    [4; 8]
+}
+
+#[create_store(v4 = strides(), crate = "crate::stand_in")]
+struct Store;
+
+fn main() {}
@@ -0,0 +1,6 @@
+use rotonda_macros::create_store;
+
+#[create_store(v4 = [4; 8], v5 = [4; 8])]
+struct Store;
+
+fn main() {}
@@ -0,0 +1,7 @@
+use rotonda_macros::create_store;
+
+#[create_store]
+#[repr(C)]
+struct Store;
+
+fn main() {}
@@ -0,0 +1,6 @@
+use rotonda_macros::create_store;
+
+#[create_store(([4; 8], [4; 32], [4; 8]))]
+struct Store;
+
+fn main() {}
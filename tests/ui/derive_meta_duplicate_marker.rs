@@ -0,0 +1,11 @@
+use rotonda_macros::Meta;
+
+#[derive(Clone, Meta)]
+struct Route {
+    #[meta(local_pref)]
+    local_pref: u32,
+    #[meta(local_pref)]
+    weight: u32,
+}
+
+fn main() {}
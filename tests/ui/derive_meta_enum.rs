@@ -0,0 +1,9 @@
+use rotonda_macros::Meta;
+
+#[derive(Clone, Meta)]
+enum Route {
+    Internal,
+    External,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use rotonda_macros::Meta;
+
+#[derive(Clone, Meta)]
+#[meta(tbi = u32)]
+struct Route {
+    #[meta(local_pref)]
+    local_pref: u32,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use rotonda_macros::Meta;
+
+#[derive(Clone, Meta)]
+struct Route {
+    #[meta(weight)]
+    weight: u32,
+}
+
+fn main() {}
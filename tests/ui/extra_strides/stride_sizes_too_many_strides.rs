@@ -0,0 +1,6 @@
+use rotonda_macros::stride_sizes;
+
+#[stride_sizes(IPv6, [1; 43])]
+struct Buckets;
+
+fn main() {}
@@ -0,0 +1,16 @@
+use rotonda_macros::stride_sizes;
+
+#[stride_sizes(
+    IPv4,
+    [4; 8],
+    bucket_map = [
+        [0], [1], [2], [3], [4], [5], [6], [7], [8], [9], [10], [11], [12],
+        [12, 13], [12, 14], [12, 15], [12, 16], [12, 17], [12, 18],
+        [12, 19], [12, 20], [12, 21], [12, 22], [12, 23], [12, 24],
+        [12, 24, 25], [12, 24, 26], [12, 24, 27], [12, 24, 28],
+        [24, 12, 29], [12, 24, 30], [12, 0, 31], [12, 24],
+    ]
+)]
+struct Buckets;
+
+fn main() {}
@@ -0,0 +1,8 @@
+use rotonda_macros::stride_sizes;
+
+const FOUR: u8 = 4;
+
+#[stride_sizes(IPv4, [4, 4, FOUR, 4, 4, 4, 4, 4])]
+struct Buckets;
+
+fn main() {}
@@ -0,0 +1,8 @@
+use rotonda_macros::stride_sizes;
+
+const EIGHT: usize = 8;
+
+#[stride_sizes(IPv4, [4; EIGHT])]
+struct Buckets;
+
+fn main() {}
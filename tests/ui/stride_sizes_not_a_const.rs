@@ -0,0 +1,8 @@
+use rotonda_macros::stride_sizes;
+
+const IP4: [u8; 8] = [4; 8];
+
+#[stride_sizes(IPv4, IP4)]
+struct Buckets;
+
+fn main() {}
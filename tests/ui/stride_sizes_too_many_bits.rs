@@ -0,0 +1,6 @@
+use rotonda_macros::stride_sizes;
+
+#[stride_sizes(IPv4, [5, 5, 5, 5, 5, 4, 5])]
+struct Buckets;
+
+fn main() {}
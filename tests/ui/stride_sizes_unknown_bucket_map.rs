@@ -0,0 +1,6 @@
+use rotonda_macros::stride_sizes;
+
+#[stride_sizes(IPv4, [4; 8], bucket_map = compact)]
+struct Buckets;
+
+fn main() {}
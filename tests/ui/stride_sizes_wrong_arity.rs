@@ -0,0 +1,6 @@
+use rotonda_macros::stride_sizes;
+
+#[stride_sizes((IPv4, [4; 8], [4; 8]))]
+struct Buckets;
+
+fn main() {}
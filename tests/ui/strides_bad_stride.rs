@@ -0,0 +1,8 @@
+use rotonda_macros::strides;
+
+strides! {
+    const IP4 = [4; 8];
+    const IP6 = [16, 4, 4, 16];
+}
+
+fn main() {}